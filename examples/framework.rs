@@ -107,24 +107,14 @@ async fn setup<E: Example>(title: &str) -> Setup {
     log::info!("Initializing the surface...");
 
     let backend = if let Ok(backend) = std::env::var("WGPU_BACKEND") {
-        match backend.to_lowercase().as_str() {
-            "vulkan" => wgpu::BackendBit::VULKAN,
-            "metal" => wgpu::BackendBit::METAL,
-            "dx12" => wgpu::BackendBit::DX12,
-            "dx11" => wgpu::BackendBit::DX11,
-            "gl" => wgpu::BackendBit::GL,
-            "webgpu" => wgpu::BackendBit::BROWSER_WEBGPU,
-            other => panic!("Unknown backend: {}", other),
-        }
+        wgpu::util::parse_backend_bits(&backend)
+            .unwrap_or_else(|| panic!("Unknown backend: {}", backend))
     } else {
         wgpu::BackendBit::PRIMARY
     };
     let power_preference = if let Ok(power_preference) = std::env::var("WGPU_POWER_PREF") {
-        match power_preference.to_lowercase().as_str() {
-            "low" => wgpu::PowerPreference::LowPower,
-            "high" => wgpu::PowerPreference::HighPerformance,
-            other => panic!("Unknown power preference: {}", other),
-        }
+        wgpu::util::parse_power_preference(&power_preference)
+            .unwrap_or_else(|| panic!("Unknown power preference: {}", power_preference))
     } else {
         wgpu::PowerPreference::default()
     };
@@ -19,7 +19,10 @@ use std::{
     marker::PhantomData,
     ops::Range,
     slice,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
 };
 
 const LABEL: &str = "label";
@@ -76,7 +79,7 @@ impl Context {
             label: label.unwrap_or_default().to_string(),
             label_key,
         };
-        let sink = sink_mutex.lock();
+        let mut sink = sink_mutex.lock();
         let mut source_opt: Option<&(dyn Error + 'static)> = Some(&error);
         while let Some(source) = source_opt {
             if let Some(wgc::device::DeviceError::OutOfMemory) =
@@ -582,7 +585,7 @@ fn map_pass_channel<V: Copy + Default>(
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct Device {
     id: wgc::id::DeviceId,
     error_sink: ErrorSink,
@@ -639,6 +642,7 @@ impl crate::Context for Context {
     type RequestDeviceFuture =
         Ready<Result<(Self::DeviceId, Self::QueueId), crate::RequestDeviceError>>;
     type MapAsyncFuture = native_gpu_future::GpuFuture<Result<(), crate::BufferAsyncError>>;
+    type PopErrorScopeFuture = Ready<Option<crate::Error>>;
 
     fn init(backends: wgt::BackendBit) -> Self {
         Self(wgc::hub::Global::new(
@@ -811,6 +815,36 @@ impl crate::Context for Context {
         let source = match desc.source {
             ShaderSource::SpirV(ref spv) => wgc::pipeline::ShaderModuleSource::SpirV(Borrowed(spv)),
             ShaderSource::Wgsl(ref code) => wgc::pipeline::ShaderModuleSource::Wgsl(Borrowed(code)),
+            // wgpu-core takes naga modules by value, but the descriptor is only borrowed and
+            // naga modules can't be cloned, so they go through SPIR-V instead
+            #[cfg(feature = "naga")]
+            ShaderSource::Naga(ref module) => match crate::naga_to_spirv(module, desc.flags) {
+                Ok(words) => {
+                    wgc::pipeline::ShaderModuleSource::SpirV(std::borrow::Cow::Owned(words))
+                }
+                Err(cause) => {
+                    // Validated empty SPIR-V only fails to parse, giving an invalid module
+                    // without reaching the backend. Its own error is superseded by `cause`.
+                    let descriptor = wgc::pipeline::ShaderModuleDescriptor {
+                        label: desc.label.map(Borrowed),
+                        flags: wgt::ShaderFlags::VALIDATION,
+                    };
+                    let (id, _) = wgc::gfx_select!(device.id => global.device_create_shader_module(
+                        device.id,
+                        &descriptor,
+                        wgc::pipeline::ShaderModuleSource::SpirV(Borrowed(&[])),
+                        PhantomData
+                    ));
+                    self.handle_error(
+                        &device.error_sink,
+                        cause,
+                        LABEL,
+                        desc.label,
+                        "Device::create_shader_module",
+                    );
+                    return id;
+                }
+            },
         };
         let (id, error) = wgc::gfx_select!(
             device.id => global.device_create_shader_module(device.id, &descriptor, source, PhantomData)
@@ -1287,7 +1321,8 @@ impl crate::Context for Context {
         match wgc::gfx_select!(device.id => global.device_poll(
             device.id,
             match maintain {
-                crate::Maintain::Poll => false,
+                // Waiting for a single submission is polling, see `Device::poll`
+                crate::Maintain::Poll | crate::Maintain::WaitForSubmissionIndex(_) => false,
                 crate::Maintain::Wait => true,
             }
         )) {
@@ -1305,22 +1340,47 @@ impl crate::Context for Context {
         error_sink.uncaptured_handler = Box::new(handler);
     }
 
+    fn device_push_error_scope(&self, device: &Self::DeviceId, filter: crate::ErrorFilter) {
+        let mut error_sink = device.error_sink.lock();
+        error_sink.scopes.push(ErrorScope {
+            error: None,
+            filter,
+        });
+    }
+
+    fn device_pop_error_scope(&self, device: &Self::DeviceId) -> Self::PopErrorScopeFuture {
+        let mut error_sink = device.error_sink.lock();
+        let scope = error_sink
+            .scopes
+            .pop()
+            .expect("Device::pop_error_scope called without a matching push");
+        ready(scope.error)
+    }
+
     fn buffer_map_async(
         &self,
         buffer: &Self::BufferId,
         mode: MapMode,
         range: Range<wgt::BufferAddress>,
+        mapped: Arc<AtomicBool>,
     ) -> Self::MapAsyncFuture {
         let (future, completion) = native_gpu_future::new_gpu_future();
 
+        struct MapCallback {
+            completion: native_gpu_future::GpuFutureCompletion<Result<(), crate::BufferAsyncError>>,
+            mapped: Arc<AtomicBool>,
+        }
+
         extern "C" fn buffer_map_future_wrapper(
             status: wgc::resource::BufferMapAsyncStatus,
             user_data: *mut u8,
         ) {
-            let completion =
-                unsafe { native_gpu_future::GpuFutureCompletion::from_raw(user_data as _) };
-            completion.complete(match status {
-                wgc::resource::BufferMapAsyncStatus::Success => Ok(()),
+            let callback = unsafe { Box::from_raw(user_data as *mut MapCallback) };
+            callback.completion.complete(match status {
+                wgc::resource::BufferMapAsyncStatus::Success => {
+                    callback.mapped.store(true, Ordering::Release);
+                    Ok(())
+                }
                 _ => Err(crate::BufferAsyncError),
             })
         }
@@ -1331,7 +1391,7 @@ impl crate::Context for Context {
                 MapMode::Write => wgc::device::HostMap::Write,
             },
             callback: buffer_map_future_wrapper,
-            user_data: completion.into_raw() as _,
+            user_data: Box::into_raw(Box::new(MapCallback { completion, mapped })) as _,
         };
 
         let global = &self.0;
@@ -1917,19 +1977,43 @@ pub(crate) struct SwapChainOutputDetail {
 
 type ErrorSink = Arc<Mutex<ErrorSinkRaw>>;
 
+struct ErrorScope {
+    error: Option<crate::Error>,
+    filter: crate::ErrorFilter,
+}
+
 struct ErrorSinkRaw {
+    scopes: Vec<ErrorScope>,
     uncaptured_handler: Box<dyn crate::UncapturedErrorHandler>,
 }
 
 impl ErrorSinkRaw {
     fn new() -> ErrorSinkRaw {
         ErrorSinkRaw {
+            scopes: Vec::new(),
             uncaptured_handler: Box::from(default_error_handler),
         }
     }
 
-    fn handle_error(&self, err: crate::Error) {
-        (self.uncaptured_handler)(err);
+    fn handle_error(&mut self, err: crate::Error) {
+        let filter = match err {
+            crate::Error::OutOfMemoryError { .. } => crate::ErrorFilter::OutOfMemory,
+            crate::Error::ValidationError { .. } => crate::ErrorFilter::Validation,
+        };
+        match self
+            .scopes
+            .iter_mut()
+            .rev()
+            .find(|scope| scope.filter == filter)
+        {
+            Some(scope) => {
+                // Only the first error is kept
+                if scope.error.is_none() {
+                    scope.error = Some(err);
+                }
+            }
+            None => (self.uncaptured_handler)(err),
+        }
     }
 }
 
@@ -3,6 +3,10 @@ use std::{
     future::Future,
     ops::Range,
     pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     task::{self, Poll},
 };
 use wasm_bindgen::prelude::*;
@@ -885,6 +889,43 @@ fn future_map_async(result: JsFutureResult) -> Result<(), crate::BufferAsyncErro
     result.map(|_| ()).map_err(|_| crate::BufferAsyncError)
 }
 
+fn future_pop_error_scope(result: JsFutureResult) -> Option<crate::Error> {
+    match result {
+        Ok(js_value) if js_value.is_null() || js_value.is_undefined() => None,
+        Ok(js_value) => Some(match js_value.dyn_into::<web_sys::GpuValidationError>() {
+            Ok(validation_error) => {
+                let description = validation_error.message();
+                crate::Error::ValidationError {
+                    source: Box::new(WebError(description.clone())),
+                    description,
+                }
+            }
+            Err(_) => crate::Error::OutOfMemoryError {
+                source: Box::new(WebError("Out of Memory".to_string())),
+            },
+        }),
+        Err(js_error) => {
+            let description = format!("{:?}", js_error);
+            Some(crate::Error::ValidationError {
+                source: Box::new(WebError(description.clone())),
+                description,
+            })
+        }
+    }
+}
+
+/// Error reported by the browser's WebGPU implementation.
+#[derive(Debug)]
+struct WebError(String);
+
+impl fmt::Display for WebError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for WebError {}
+
 impl crate::Context for Context {
     type AdapterId = Sendable<web_sys::GpuAdapter>;
     type DeviceId = Sendable<web_sys::GpuDevice>;
@@ -921,8 +962,10 @@ impl crate::Context for Context {
     >;
     type MapAsyncFuture = MakeSendFuture<
         wasm_bindgen_futures::JsFuture,
-        fn(JsFutureResult) -> Result<(), crate::BufferAsyncError>,
+        Box<dyn Fn(JsFutureResult) -> Result<(), crate::BufferAsyncError>>,
     >;
+    type PopErrorScopeFuture =
+        MakeSendFuture<wasm_bindgen_futures::JsFuture, fn(JsFutureResult) -> Option<crate::Error>>;
 
     fn init(_backends: wgt::BackendBit) -> Self {
         Context(web_sys::window().unwrap().navigator().gpu())
@@ -1157,6 +1200,12 @@ impl crate::Context for Context {
                 let words = spv::write_vec(&module, &analysis, &options).unwrap();
                 web_sys::GpuShaderModuleDescriptor::new(&js_sys::Uint32Array::from(&words[..]))
             }
+            #[cfg(feature = "naga")]
+            crate::ShaderSource::Naga(ref module) => {
+                let words = crate::naga_to_spirv(module, desc.flags)
+                    .unwrap_or_else(|err| panic!("{}", err));
+                web_sys::GpuShaderModuleDescriptor::new(&js_sys::Uint32Array::from(&words[..]))
+            }
         };
         if let Some(ref label) = desc.label {
             descriptor.label(label);
@@ -1543,11 +1592,27 @@ impl crate::Context for Context {
         // TODO:
     }
 
+    fn device_push_error_scope(&self, device: &Self::DeviceId, filter: crate::ErrorFilter) {
+        device.0.push_error_scope(match filter {
+            crate::ErrorFilter::OutOfMemory => web_sys::GpuErrorFilter::OutOfMemory,
+            crate::ErrorFilter::Validation => web_sys::GpuErrorFilter::Validation,
+        });
+    }
+
+    fn device_pop_error_scope(&self, device: &Self::DeviceId) -> Self::PopErrorScopeFuture {
+        let error_promise = device.0.pop_error_scope();
+        MakeSendFuture::new(
+            wasm_bindgen_futures::JsFuture::from(error_promise),
+            future_pop_error_scope,
+        )
+    }
+
     fn buffer_map_async(
         &self,
         buffer: &Self::BufferId,
         mode: crate::MapMode,
         range: Range<wgt::BufferAddress>,
+        mapped: Arc<AtomicBool>,
     ) -> Self::MapAsyncFuture {
         let map_promise = buffer.0.map_async_with_f64_and_f64(
             map_map_mode(mode),
@@ -1555,9 +1620,16 @@ impl crate::Context for Context {
             (range.end - range.start) as f64,
         );
 
+        // The promise is only seen to resolve when the future is polled
         MakeSendFuture::new(
             wasm_bindgen_futures::JsFuture::from(map_promise),
-            future_map_async,
+            Box::new(move |result| {
+                let result = future_map_async(result);
+                if result.is_ok() {
+                    mapped.store(true, Ordering::Release);
+                }
+                result
+            }),
         )
     }
 
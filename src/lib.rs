@@ -12,13 +12,18 @@ mod macros;
 
 use std::{
     borrow::Cow,
+    collections::VecDeque,
     error,
     fmt::{Debug, Display},
     future::Future,
     marker::PhantomData,
     num::{NonZeroU32, NonZeroU8},
     ops::{Bound, Range, RangeBounds},
-    sync::Arc,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     thread,
 };
 
@@ -30,8 +35,8 @@ pub use wgt::{
     BufferSize, BufferUsage, Color, ColorTargetState, ColorWrite, CommandBufferDescriptor,
     CompareFunction, DepthBiasState, DepthStencilState, DeviceType, DownlevelFlags,
     DownlevelProperties, DynamicOffset, Extent3d, Face, Features, FilterMode, FrontFace,
-    ImageDataLayout, IndexFormat, InputStepMode, Limits, MultisampleState, Origin3d,
-    PipelineStatisticsTypes, PolygonMode, PowerPreference, PresentMode, PrimitiveState,
+    ImageDataLayout, ImageSubresourceRange, IndexFormat, InputStepMode, Limits, MultisampleState,
+    Origin3d, PipelineStatisticsTypes, PolygonMode, PowerPreference, PresentMode, PrimitiveState,
     PrimitiveTopology, PushConstantRange, QuerySetDescriptor, QueryType, SamplerBorderColor,
     ShaderFlags, ShaderLocation, ShaderModel, ShaderStage, StencilFaceState, StencilOperation,
     StencilState, StorageTextureAccess, SwapChainDescriptor, SwapChainStatus, TextureAspect,
@@ -41,6 +46,9 @@ pub use wgt::{
     PUSH_CONSTANT_ALIGNMENT, QUERY_SET_MAX_QUERIES, QUERY_SIZE, VERTEX_STRIDE_ALIGNMENT,
 };
 
+#[cfg(feature = "derive")]
+pub use wgpu_macros::VertexLayout;
+
 use backend::{BufferMappedRange, Context as C};
 
 trait ComputePassInner<Ctx: Context> {
@@ -154,7 +162,7 @@ trait RenderPassInner<Ctx: Context>: RenderInner<Ctx> {
 
 trait Context: Debug + Send + Sized + Sync {
     type AdapterId: Debug + Send + Sync + 'static;
-    type DeviceId: Debug + Send + Sync + 'static;
+    type DeviceId: Clone + Debug + Send + Sync + 'static;
     type QueueId: Debug + Send + Sync + 'static;
     type ShaderModuleId: Debug + Send + Sync + 'static;
     type BindGroupLayoutId: Debug + Send + Sync + 'static;
@@ -182,6 +190,7 @@ trait Context: Debug + Send + Sized + Sync {
     type RequestDeviceFuture: Future<Output = Result<(Self::DeviceId, Self::QueueId), RequestDeviceError>>
         + Send;
     type MapAsyncFuture: Future<Output = Result<(), BufferAsyncError>> + Send;
+    type PopErrorScopeFuture: Future<Output = Option<Error>> + Send;
 
     fn init(backends: BackendBit) -> Self;
     fn instance_create_surface(
@@ -290,12 +299,16 @@ trait Context: Debug + Send + Sized + Sync {
         device: &Self::DeviceId,
         handler: impl UncapturedErrorHandler,
     );
+    fn device_push_error_scope(&self, device: &Self::DeviceId, filter: ErrorFilter);
+    fn device_pop_error_scope(&self, device: &Self::DeviceId) -> Self::PopErrorScopeFuture;
 
+    /// `mapped` is set once the mapping succeeded, as soon as the backend learns of it.
     fn buffer_map_async(
         &self,
         buffer: &Self::BufferId,
         mode: MapMode,
         range: Range<BufferAddress>,
+        mapped: Arc<AtomicBool>,
     ) -> Self::MapAsyncFuture;
     fn buffer_get_mapped_range(
         &self,
@@ -493,6 +506,234 @@ impl Drop for Adapter {
 pub struct Device {
     context: Arc<C>,
     id: <C as Context>::DeviceId,
+    /// Directory given to [`Adapter::request_device`] for API call tracing.
+    trace_path: Option<std::path::PathBuf>,
+    shared: Arc<DeviceShared>,
+}
+
+/// Size of the zero-filled buffer [`CommandEncoder::clear_buffer`] and
+/// [`CommandEncoder::clear_texture`] copy from, in as many copies as needed.
+const CLEAR_SOURCE_SIZE: BufferAddress = 1 << 20;
+
+/// State a [`Device`] shares with the objects it creates, which outlive any borrow of it.
+#[derive(Debug)]
+struct DeviceShared {
+    context: Arc<C>,
+    id: <C as Context>::DeviceId,
+    /// Zero-filled buffer the clears of command encoders copy from, created on first use.
+    clear_source: Mutex<Option<Arc<Buffer>>>,
+    /// Copies out of the staging buffers of dropped [`QueueWriteBufferView`]s, submitted
+    /// ahead of the next [`Queue::submit`].
+    staged_writes: Mutex<Vec<CommandBuffer>>,
+    submissions: Mutex<SubmissionTracker>,
+    /// Generator reused by [`util::generate_mipmaps`], created on first use.
+    mipmap_generator: Mutex<Option<util::MipmapGenerator>>,
+}
+
+/// Interval between the non-blocking polls of a device waiting for a submission.
+#[cfg(not(target_arch = "wasm32"))]
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(1);
+
+type WorkDoneCallback = Box<dyn FnOnce() + Send>;
+
+/// Submissions made to a queue whose completion wasn't observed yet.
+///
+/// The device can't report which submissions are done, so every submission writes to a
+/// small fence buffer, which can only be mapped once the submission completed. Fences are
+/// reused once their submission is done.
+#[derive(Default)]
+struct SubmissionTracker {
+    /// Index of the last submission made.
+    last_index: u64,
+    /// Submissions not known to be done, oldest first.
+    pending: VecDeque<PendingSubmission>,
+    /// Unmapped fences of completed submissions.
+    free_fences: Vec<Buffer>,
+    /// Callbacks registered for submissions already done, run on the next poll.
+    ready_callbacks: Vec<WorkDoneCallback>,
+}
+
+struct PendingSubmission {
+    index: u64,
+    fence: Buffer,
+    mapping: Pin<Box<dyn Future<Output = Result<(), BufferAsyncError>> + Send>>,
+    callbacks: Vec<WorkDoneCallback>,
+}
+
+impl Debug for SubmissionTracker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubmissionTracker")
+            .field("last_index", &self.last_index)
+            .field("pending", &self.pending.len())
+            .field("free_fences", &self.free_fences.len())
+            .finish()
+    }
+}
+
+impl SubmissionTracker {
+    fn is_done(&self, index: u64) -> bool {
+        self.pending
+            .front()
+            .map_or(true, |submission| submission.index > index)
+    }
+
+    fn on_done(&mut self, index: u64, callback: WorkDoneCallback) {
+        match self
+            .pending
+            .iter_mut()
+            .find(|submission| submission.index == index)
+        {
+            Some(submission) => submission.callbacks.push(callback),
+            None => self.ready_callbacks.push(callback),
+        }
+    }
+}
+
+impl DeviceShared {
+    fn create_buffer(&self, desc: &BufferDescriptor) -> Buffer {
+        let mut map_context = MapContext::new(desc.size);
+        if desc.mapped_at_creation {
+            map_context
+                .map(0..desc.size, MapMode::Write)
+                .store(true, Ordering::Release);
+        }
+        Buffer {
+            context: Arc::clone(&self.context),
+            id: Context::device_create_buffer(&*self.context, &self.id, desc),
+            map_context: Mutex::new(map_context),
+            size: desc.size,
+            usage: desc.usage,
+        }
+    }
+
+    fn create_command_encoder(self: &Arc<Self>, desc: &CommandEncoderDescriptor) -> CommandEncoder {
+        CommandEncoder {
+            context: Arc::clone(&self.context),
+            id: Some(Context::device_create_command_encoder(
+                &*self.context,
+                &self.id,
+                desc,
+            )),
+            device: Arc::clone(self),
+            _p: Default::default(),
+        }
+    }
+
+    fn clear_source(&self) -> Arc<Buffer> {
+        let mut clear_source = self.clear_source.lock();
+        let buffer = clear_source.get_or_insert_with(|| {
+            // Buffers are zero-initialized, so nothing has to be written to it
+            Arc::new(self.create_buffer(&BufferDescriptor {
+                label: Some("clear source"),
+                size: CLEAR_SOURCE_SIZE,
+                usage: BufferUsage::COPY_SRC,
+                mapped_at_creation: false,
+            }))
+        });
+        Arc::clone(buffer)
+    }
+
+    /// Submits `command_buffers` to `queue`, along with a write to a fence on native.
+    fn submit(
+        &self,
+        queue: &<C as Context>::QueueId,
+        command_buffers: impl Iterator<Item = <C as Context>::CommandBufferId>,
+    ) -> SubmissionIndex {
+        let mut submissions = self.submissions.lock();
+        #[cfg(not(target_arch = "wasm32"))]
+        let fence = {
+            let fence = submissions.free_fences.pop().unwrap_or_else(|| {
+                self.create_buffer(&BufferDescriptor {
+                    label: Some("submission fence"),
+                    size: COPY_BUFFER_ALIGNMENT,
+                    usage: BufferUsage::MAP_READ | BufferUsage::COPY_DST,
+                    mapped_at_creation: false,
+                })
+            });
+            // Writes are part of the next submission, so the fence is mapped after it
+            Context::queue_write_buffer(
+                &*self.context,
+                queue,
+                &fence.id,
+                0,
+                &[0; COPY_BUFFER_ALIGNMENT as usize],
+            );
+            fence
+        };
+        Context::queue_submit(&*self.context, queue, command_buffers);
+        submissions.last_index += 1;
+        let index = submissions.last_index;
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            let mapping = Box::pin(fence.slice(..).map_async(MapMode::Read));
+            submissions.pending.push_back(PendingSubmission {
+                index,
+                fence,
+                mapping,
+                callbacks: Vec::new(),
+            });
+            drop(submissions);
+            // Submitting maintains the device as well, which may have completed fences
+            self.update_submissions();
+        }
+        SubmissionIndex(index)
+    }
+
+    /// Retires the submissions whose fence got mapped, running their callbacks.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn update_submissions(&self) {
+        use std::task;
+
+        let waker = util::noop_waker();
+        let mut cx = task::Context::from_waker(&waker);
+        let mut callbacks = Vec::new();
+        {
+            let mut guard = self.submissions.lock();
+            let submissions = &mut *guard;
+            callbacks.append(&mut submissions.ready_callbacks);
+            while let Some(submission) = submissions.pending.front_mut() {
+                let mapped = match submission.mapping.as_mut().poll(&mut cx) {
+                    task::Poll::Ready(result) => result.is_ok(),
+                    task::Poll::Pending => break,
+                };
+                let submission = submissions.pending.pop_front().unwrap();
+                // The device is lost if the fence can't be mapped, and the callbacks are
+                // dropped as the work will never complete
+                if mapped {
+                    callbacks.extend(submission.callbacks);
+                    submission.fence.unmap();
+                    submissions.free_fences.push(submission.fence);
+                }
+            }
+        }
+        // Run outside of the lock, so callbacks can submit
+        for callback in callbacks {
+            callback();
+        }
+    }
+
+    /// Polls the device until the submission `index` is done, without waiting for the
+    /// submissions made after it.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn wait_for_submission(&self, index: u64) {
+        loop {
+            Context::device_poll(&*self.context, &self.id, Maintain::Poll);
+            self.update_submissions();
+            if self.submissions.lock().is_done(index) {
+                return;
+            }
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+
+    /// Releases the resources held for the device, which can't outlive it.
+    fn release(&self) {
+        self.clear_source.lock().take();
+        self.staged_writes.lock().clear();
+        self.mipmap_generator.lock().take();
+        let submissions = std::mem::take(&mut *self.submissions.lock());
+        drop(submissions);
+    }
 }
 
 /// Passed to [`Device::poll`] to control if it should block or not. This has no effect on
@@ -503,14 +744,27 @@ pub enum Maintain {
     Wait,
     /// Don't block
     Poll,
+    /// Block until the submission with the given index is done, but not for the submissions
+    /// made after it.
+    WaitForSubmissionIndex(SubmissionIndex),
 }
 
+/// Identifies a [`Queue::submit`] call, to wait for with [`Maintain::WaitForSubmissionIndex`].
+///
+/// Later submissions have greater indices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SubmissionIndex(u64);
+
 /// The main purpose of this struct is to resolve mapped ranges (convert sizes
 /// to end points), and to ensure that the sub-ranges don't intersect.
 #[derive(Debug)]
 struct MapContext {
     total_size: BufferAddress,
     initial_range: Range<BufferAddress>,
+    mode: Option<MapMode>,
+    /// Set by the backend once the mapping of `initial_range` is accessible, replaced for
+    /// every mapping so late results of earlier ones can't set it.
+    mapped: Arc<AtomicBool>,
     sub_ranges: Vec<Range<BufferAddress>>,
 }
 
@@ -519,12 +773,28 @@ impl MapContext {
         MapContext {
             total_size,
             initial_range: 0..0,
+            mode: None,
+            mapped: Arc::new(AtomicBool::new(false)),
             sub_ranges: Vec::new(),
         }
     }
 
+    /// Starts a mapping of `range`, returning the flag to set once it is accessible.
+    fn map(&mut self, range: Range<BufferAddress>, mode: MapMode) -> Arc<AtomicBool> {
+        self.initial_range = range;
+        self.mode = Some(mode);
+        self.mapped = Arc::new(AtomicBool::new(false));
+        Arc::clone(&self.mapped)
+    }
+
+    fn is_mapped(&self) -> bool {
+        self.mapped.load(Ordering::Acquire)
+    }
+
     fn reset(&mut self) {
         self.initial_range = 0..0;
+        self.mode = None;
+        self.mapped = Arc::new(AtomicBool::new(false));
 
         assert!(
             self.sub_ranges.is_empty(),
@@ -572,6 +842,7 @@ pub struct Buffer {
     context: Arc<C>,
     id: <C as Context>::BufferId,
     map_context: Mutex<MapContext>,
+    size: BufferAddress,
     usage: BufferUsage,
 }
 
@@ -595,6 +866,12 @@ pub struct Texture {
     context: Arc<C>,
     id: <C as Context>::TextureId,
     owned: bool,
+    size: Extent3d,
+    mip_level_count: u32,
+    sample_count: u32,
+    dimension: TextureDimension,
+    format: TextureFormat,
+    usage: TextureUsage,
 }
 
 /// Handle to a texture view.
@@ -637,6 +914,45 @@ pub struct Surface {
     id: <C as Context>::SurfaceId,
 }
 
+impl Surface {
+    /// Gathers what is known about this surface when used with `adapter`.
+    ///
+    /// Only the adapter's preferred swap chain format can be queried, so it is the single
+    /// reported format, if any. The supported present modes and usages can't be queried
+    /// yet, and are reported as unknown.
+    pub fn capabilities_snapshot(&self, adapter: &Adapter) -> util::SurfaceCapabilitiesSnapshot {
+        util::SurfaceCapabilitiesSnapshot {
+            formats: adapter
+                .get_swap_chain_preferred_format(self)
+                .into_iter()
+                .collect(),
+            present_modes: None,
+            usages: None,
+        }
+    }
+
+    /// Creates a swap chain for this surface, substituting the values of `requested` that
+    /// `adapter` can't present with supported ones.
+    ///
+    /// Only values known from [`capabilities_snapshot`](Surface::capabilities_snapshot) are
+    /// substituted, which currently is a format other than the preferred one. The present
+    /// mode and usages are passed through unchanged, as they can't be queried yet.
+    ///
+    /// Substitutions are logged, see
+    /// [`SurfaceCapabilitiesSnapshot::resolve`](util::SurfaceCapabilitiesSnapshot::resolve).
+    /// The returned descriptor is the one the swap chain was created with, to store and
+    /// update on resize.
+    pub fn configure_checked(
+        &self,
+        device: &Device,
+        requested: &SwapChainDescriptor,
+        adapter: &Adapter,
+    ) -> (SwapChain, SwapChainDescriptor) {
+        let desc = self.capabilities_snapshot(adapter).resolve(requested);
+        (device.create_swap_chain(self, &desc), desc)
+    }
+}
+
 impl Drop for Surface {
     fn drop(&mut self) {
         if !thread::panicking() {
@@ -728,6 +1044,19 @@ pub enum ShaderSource<'a> {
     ///
     /// Note: WGSL is not yet supported on the Web.
     Wgsl(Cow<'a, str>),
+    /// Module in naga's intermediate representation, e.g. built programmatically.
+    ///
+    /// The module is validated by naga according to the
+    /// [`flags`](ShaderModuleDescriptor::flags) of the descriptor and translated to SPIR-V
+    /// when the shader module is created. The naga version used here can't clone modules,
+    /// so the descriptor owns it. An invalid module is reported like any other invalid
+    /// shader, and the resulting shader module is invalid.
+    ///
+    /// # Panics
+    ///
+    /// - On the web, the module fails to validate or to translate to SPIR-V.
+    #[cfg(feature = "naga")]
+    Naga(naga::Module),
 }
 
 /// Descriptor for a shader module.
@@ -740,6 +1069,46 @@ pub struct ShaderModuleDescriptor<'a> {
     pub flags: ShaderFlags,
 }
 
+/// A [`ShaderSource::Naga`] module failed to validate or to translate to SPIR-V.
+#[cfg(feature = "naga")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct NagaModuleError(String);
+
+#[cfg(feature = "naga")]
+impl Display for NagaModuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid naga module: {}", self.0)
+    }
+}
+
+#[cfg(feature = "naga")]
+impl error::Error for NagaModuleError {}
+
+/// Translates a [`ShaderSource::Naga`] module to SPIR-V, validating it all the way when
+/// `flags` asks for validation.
+#[cfg(feature = "naga")]
+pub(crate) fn naga_to_spirv(
+    module: &naga::Module,
+    flags: ShaderFlags,
+) -> Result<Vec<u32>, NagaModuleError> {
+    use naga::{back::spv, valid::Validator};
+
+    let validation_flags = if flags.contains(ShaderFlags::VALIDATION) {
+        naga::valid::ValidationFlags::all()
+    } else {
+        naga::valid::ValidationFlags::empty()
+    };
+    let info = Validator::new(validation_flags, naga::valid::Capabilities::all())
+        .validate(module)
+        .map_err(|err| NagaModuleError(format!("{:?}", err)))?;
+    let options = spv::Options {
+        lang_version: (1, 0),
+        flags: spv::WriterFlags::empty(),
+        capabilities: None,
+    };
+    spv::write_vec(module, &info, &options).map_err(|err| NagaModuleError(format!("{:?}", err)))
+}
+
 /// Handle to a pipeline layout.
 ///
 /// A `PipelineLayout` object describes the available binding groups of a pipeline.
@@ -777,6 +1146,9 @@ impl Drop for RenderPipeline {
 
 impl RenderPipeline {
     /// Get an object representing the bind group layout at a given index.
+    ///
+    /// This is mostly useful for pipelines created without an explicit layout, to create
+    /// bind groups matching the layout derived from the shader.
     pub fn get_bind_group_layout(&self, index: u32) -> BindGroupLayout {
         let context = Arc::clone(&self.context);
         BindGroupLayout {
@@ -808,6 +1180,9 @@ impl Drop for ComputePipeline {
 
 impl ComputePipeline {
     /// Get an object representing the bind group layout at a given index.
+    ///
+    /// This is mostly useful for pipelines created without an explicit layout, to create
+    /// bind groups matching the layout derived from the shader.
     pub fn get_bind_group_layout(&self, index: u32) -> BindGroupLayout {
         let context = Arc::clone(&self.context);
         BindGroupLayout {
@@ -851,6 +1226,7 @@ impl Drop for CommandBuffer {
 pub struct CommandEncoder {
     context: Arc<C>,
     id: Option<<C as Context>::CommandEncoderId>,
+    device: Arc<DeviceShared>,
     /// This type should be !Send !Sync, because it represents an allocation on this thread's
     /// command buffer.
     _p: PhantomData<*const u8>,
@@ -891,6 +1267,7 @@ pub struct RenderBundleEncoder<'a> {
     context: Arc<C>,
     id: <C as Context>::RenderBundleEncoderId,
     _parent: &'a Device,
+    draw_count: u32,
     /// This type should be !Send !Sync, because it represents an allocation on this thread's
     /// command buffer.
     _p: PhantomData<*const u8>,
@@ -906,6 +1283,22 @@ pub struct RenderBundleEncoder<'a> {
 pub struct RenderBundle {
     context: Arc<C>,
     id: <C as Context>::RenderBundleId,
+    label: Option<String>,
+    draw_count: u32,
+}
+
+impl RenderBundle {
+    /// Debug label of the bundle, as given to [`RenderBundleEncoder::finish`].
+    ///
+    /// Useful to attribute GPU time to named bundles in profiling tools.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
+    /// Number of draw calls recorded into the bundle, indirect ones included.
+    pub fn draw_count(&self) -> u32 {
+        self.draw_count
+    }
 }
 
 impl Drop for RenderBundle {
@@ -938,6 +1331,7 @@ impl Drop for QuerySet {
 pub struct Queue {
     context: Arc<C>,
     id: <C as Context>::QueueId,
+    device: Arc<DeviceShared>,
 }
 
 /// Resource that can be bound to a pipeline.
@@ -988,6 +1382,24 @@ pub struct BufferBinding<'a> {
     pub size: Option<BufferSize>,
 }
 
+impl<'a> From<BufferBinding<'a>> for BindingResource<'a> {
+    fn from(binding: BufferBinding<'a>) -> Self {
+        BindingResource::Buffer(binding)
+    }
+}
+
+impl<'a> From<&'a Sampler> for BindingResource<'a> {
+    fn from(sampler: &'a Sampler) -> Self {
+        BindingResource::Sampler(sampler)
+    }
+}
+
+impl<'a> From<&'a TextureView> for BindingResource<'a> {
+    fn from(view: &'a TextureView) -> Self {
+        BindingResource::TextureView(view)
+    }
+}
+
 /// Operation to perform to the output attachment at the start of a renderpass.
 ///
 /// The render target must be cleared at least once before its content is loaded.
@@ -1093,6 +1505,41 @@ pub struct TextureViewDescriptor<'a> {
     pub array_layer_count: Option<NonZeroU32>,
 }
 
+impl<'a> TextureViewDescriptor<'a> {
+    /// View of a single mip level, including all the array layers.
+    ///
+    /// Fields can be overridden with struct update syntax, as with the [`SamplerDescriptor`]
+    /// presets.
+    pub fn mip(level: u32) -> Self {
+        Self {
+            base_mip_level: level,
+            mip_level_count: NonZeroU32::new(1),
+            ..Self::default()
+        }
+    }
+
+    /// 2D view of a single array layer, including all the mip levels.
+    ///
+    /// Useful to render to one face of a cube map, or one layer of an array texture.
+    pub fn layer(index: u32) -> Self {
+        Self {
+            dimension: Some(TextureViewDimension::D2),
+            base_array_layer: index,
+            array_layer_count: NonZeroU32::new(1),
+            ..Self::default()
+        }
+    }
+
+    /// 2D view of a single mip level of a single array layer.
+    pub fn mip_layer(level: u32, index: u32) -> Self {
+        Self {
+            base_mip_level: level,
+            mip_level_count: NonZeroU32::new(1),
+            ..Self::layer(index)
+        }
+    }
+}
+
 /// Describes a pipeline layout.
 ///
 /// A `PipelineLayoutDescriptor` can be used to create a pipeline layout.
@@ -1159,6 +1606,43 @@ impl Default for SamplerDescriptor<'_> {
     }
 }
 
+impl<'a> SamplerDescriptor<'a> {
+    /// Sampler using nearest filtering, clamping coordinates to the edge of the texture.
+    ///
+    /// Fields can be overridden with struct update syntax:
+    /// `SamplerDescriptor { label: Some("pixel art"), ..SamplerDescriptor::nearest_clamp() }`.
+    pub const fn nearest_clamp() -> Self {
+        Self::preset(AddressMode::ClampToEdge, FilterMode::Nearest)
+    }
+
+    /// Sampler using linear filtering, clamping coordinates to the edge of the texture.
+    pub const fn linear_clamp() -> Self {
+        Self::preset(AddressMode::ClampToEdge, FilterMode::Linear)
+    }
+
+    /// Sampler using linear filtering, repeating the texture in every direction.
+    pub const fn linear_repeat() -> Self {
+        Self::preset(AddressMode::Repeat, FilterMode::Linear)
+    }
+
+    const fn preset(address_mode: AddressMode, filter: FilterMode) -> Self {
+        Self {
+            label: None,
+            address_mode_u: address_mode,
+            address_mode_v: address_mode,
+            address_mode_w: address_mode,
+            mag_filter: filter,
+            min_filter: filter,
+            mipmap_filter: filter,
+            lod_min_clamp: 0.0,
+            lod_max_clamp: std::f32::MAX,
+            compare: None,
+            anisotropy_clamp: None,
+            border_color: None,
+        }
+    }
+}
+
 /// Bindable resource and the slot to bind it to.
 #[derive(Clone, Debug)]
 pub struct BindGroupEntry<'a> {
@@ -1235,6 +1719,9 @@ pub struct RenderPipelineDescriptor<'a> {
     /// Debug label of the pipeline. This will show up in graphics debuggers for easy identification.
     pub label: Label<'a>,
     /// The layout of bind groups for this pipeline.
+    ///
+    /// If `None`, the layout is derived from the shader, and its bind group layouts can be
+    /// retrieved with [`RenderPipeline::get_bind_group_layout`].
     pub layout: Option<&'a PipelineLayout>,
     /// The compiled vertex stage, its entry point, and the input buffers layout.
     pub vertex: VertexState<'a>,
@@ -1261,6 +1748,9 @@ pub struct ComputePipelineDescriptor<'a> {
     /// Debug label of the pipeline. This will show up in graphics debuggers for easy identification.
     pub label: Label<'a>,
     /// The layout of bind groups for this pipeline.
+    ///
+    /// If `None`, the layout is derived from the shader, and its bind group layouts can be
+    /// retrieved with [`ComputePipeline::get_bind_group_layout`].
     pub layout: Option<&'a PipelineLayout>,
     /// The compiled shader module for this stage.
     pub module: &'a ShaderModule,
@@ -1360,6 +1850,14 @@ impl Instance {
         }
     }
 
+    /// Create an new instance of wgpu, using the backends listed in the `WGPU_BACKEND`
+    /// environment variable, or all of them if it is not set or names no known backend.
+    ///
+    /// See [`util::parse_backend_bits`] for the syntax of the variable.
+    pub fn new_from_env() -> Self {
+        Self::new(util::backend_bits_from_env().unwrap_or_else(BackendBit::all))
+    }
+
     /// Retrieves all available [`Adapter`]s that match the given [`BackendBit`].
     ///
     /// # Arguments
@@ -1377,6 +1875,31 @@ impl Instance {
             })
     }
 
+    /// Picks the adapter of `backends` best matching `power_preference`, among those that can
+    /// present to `surface` if one is given.
+    ///
+    /// Discrete GPUs come first for [`PowerPreference::HighPerformance`], integrated ones for
+    /// [`PowerPreference::LowPower`], followed by virtual and software adapters. Ties are broken
+    /// by enumeration order, and any single adapter found is returned.
+    ///
+    /// An adapter is taken to be compatible with `surface` if it has a
+    /// [preferred swap chain format](Adapter::get_swap_chain_preferred_format) for it.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn find_adapter(
+        &self,
+        backends: BackendBit,
+        power_preference: PowerPreference,
+        surface: Option<&Surface>,
+    ) -> Option<Adapter> {
+        self.enumerate_adapters(backends)
+            .filter(|adapter| {
+                surface.map_or(true, |surface| {
+                    adapter.get_swap_chain_preferred_format(surface).is_some()
+                })
+            })
+            .min_by_key(|adapter| adapter_rank(adapter.get_info().device_type, power_preference))
+    }
+
     /// Retrieves an [`Adapter`] which matches the given [`RequestAdapterOptions`].
     ///
     /// Some options are "soft", so treated as non-mandatory. Others are "hard".
@@ -1451,16 +1974,28 @@ impl Adapter {
     ) -> impl Future<Output = Result<(Device, Queue), RequestDeviceError>> + Send {
         let context = Arc::clone(&self.context);
         let device = Context::adapter_request_device(&*self.context, &self.id, desc, trace_path);
+        let trace_path = trace_path.map(std::path::Path::to_path_buf);
         async move {
             device.await.map(|(device_id, queue_id)| {
+                let shared = Arc::new(DeviceShared {
+                    context: Arc::clone(&context),
+                    id: device_id.clone(),
+                    clear_source: Mutex::new(None),
+                    staged_writes: Mutex::new(Vec::new()),
+                    submissions: Mutex::new(SubmissionTracker::default()),
+                    mipmap_generator: Mutex::new(None),
+                });
                 (
                     Device {
                         context: Arc::clone(&context),
                         id: device_id,
+                        trace_path,
+                        shared: Arc::clone(&shared),
                     },
                     Queue {
                         context,
                         id: queue_id,
+                        device: shared,
                     },
                 )
             })
@@ -1511,8 +2046,55 @@ impl Device {
     /// Check for resource cleanups and mapping callbacks.
     ///
     /// no-op on the web, device is automatically polled.
+    ///
+    /// [`Maintain::WaitForSubmissionIndex`] polls without blocking until the submission is
+    /// done, sleeping briefly in between, as the device can only block until all its work
+    /// is done.
     pub fn poll(&self, maintain: Maintain) {
-        Context::device_poll(&*self.context, &self.id, maintain);
+        match maintain {
+            #[cfg(not(target_arch = "wasm32"))]
+            Maintain::WaitForSubmissionIndex(SubmissionIndex(index)) => {
+                self.shared.wait_for_submission(index)
+            }
+            _ => {
+                Context::device_poll(&*self.context, &self.id, maintain);
+                #[cfg(not(target_arch = "wasm32"))]
+                self.shared.update_submissions();
+            }
+        }
+    }
+
+    /// Polls the device until all the work submitted to it completed, or `timeout` elapsed.
+    ///
+    /// Unlike [`Maintain::Wait`], a hung GPU surfaces as a [`PollTimeout`] instead of
+    /// blocking forever. The device is polled with [`Maintain::Poll`], sleeping briefly
+    /// in between. Returns `Ok(true)` once all the work is done, including when there was
+    /// none to wait for.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn poll_timeout(&self, timeout: std::time::Duration) -> Result<bool, PollTimeout> {
+        let index = self.shared.submissions.lock().last_index;
+        poll_until(timeout, || {
+            self.poll(Maintain::Poll);
+            self.shared.submissions.lock().is_done(index)
+        })?;
+        Ok(true)
+    }
+
+    /// Whether API calls on this device are recorded into a trace.
+    ///
+    /// This requires the `trace` feature, and a trace path given to
+    /// [`Adapter::request_device`]. Tracing is not available on the web.
+    pub fn is_tracing(&self) -> bool {
+        cfg!(all(feature = "trace", not(target_arch = "wasm32"))) && self.trace_path.is_some()
+    }
+
+    /// Directory the API trace is written to, as given to [`Adapter::request_device`].
+    ///
+    /// The trace can't be started or stopped once the device is created. Every call is
+    /// written to the `trace.ron` file as it happens, so the trace can be inspected at any
+    /// point, and is complete once the device is dropped.
+    pub fn trace_path(&self) -> Option<&std::path::Path> {
+        self.trace_path.as_deref()
     }
 
     /// List all features that may be used with this device.
@@ -1529,7 +2111,7 @@ impl Device {
         Context::device_limits(&*self.context, &self.id)
     }
 
-    /// Creates a shader module from either SPIR-V or WGSL source code.
+    /// Creates a shader module from SPIR-V or WGSL source code, or from a naga module.
     pub fn create_shader_module(&self, desc: &ShaderModuleDescriptor) -> ShaderModule {
         ShaderModule {
             context: Arc::clone(&self.context),
@@ -1539,15 +2121,7 @@ impl Device {
 
     /// Creates an empty [`CommandEncoder`].
     pub fn create_command_encoder(&self, desc: &CommandEncoderDescriptor) -> CommandEncoder {
-        CommandEncoder {
-            context: Arc::clone(&self.context),
-            id: Some(Context::device_create_command_encoder(
-                &*self.context,
-                &self.id,
-                desc,
-            )),
-            _p: Default::default(),
-        }
+        self.shared.create_command_encoder(desc)
     }
 
     /// Creates an empty [`RenderBundleEncoder`].
@@ -1559,6 +2133,7 @@ impl Device {
             context: Arc::clone(&self.context),
             id: Context::device_create_render_bundle_encoder(&*self.context, &self.id, desc),
             _parent: self,
+            draw_count: 0,
             _p: Default::default(),
         }
     }
@@ -1605,16 +2180,7 @@ impl Device {
 
     /// Creates a [`Buffer`].
     pub fn create_buffer(&self, desc: &BufferDescriptor) -> Buffer {
-        let mut map_context = MapContext::new(desc.size);
-        if desc.mapped_at_creation {
-            map_context.initial_range = 0..desc.size;
-        }
-        Buffer {
-            context: Arc::clone(&self.context),
-            id: Context::device_create_buffer(&*self.context, &self.id, desc),
-            map_context: Mutex::new(map_context),
-            usage: desc.usage,
-        }
+        self.shared.create_buffer(desc)
     }
 
     /// Creates a new [`Texture`].
@@ -1625,6 +2191,12 @@ impl Device {
             context: Arc::clone(&self.context),
             id: Context::device_create_texture(&*self.context, &self.id, desc),
             owned: true,
+            size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            sample_count: desc.sample_count,
+            dimension: desc.dimension,
+            format: desc.format,
+            usage: desc.usage,
         }
     }
 
@@ -1664,6 +2236,36 @@ impl Device {
         self.context.device_on_uncaptured_error(&self.id, handler);
     }
 
+    /// Push an error scope, which captures errors matching `filter` until it is popped.
+    ///
+    /// Errors are reported to the innermost scope with a matching filter.
+    /// Errors that no scope captures are passed to the [uncaptured error handler](Device::on_uncaptured_error).
+    pub fn push_error_scope(&self, filter: ErrorFilter) {
+        Context::device_push_error_scope(&*self.context, &self.id, filter);
+    }
+
+    /// Pop the innermost error scope, resolving to the first error it captured, if any.
+    ///
+    /// # Panics
+    ///
+    /// - There is no error scope to pop.
+    pub fn pop_error_scope(&self) -> impl Future<Output = Option<Error>> + Send {
+        Context::device_pop_error_scope(&*self.context, &self.id)
+    }
+
+    /// Push an error scope that is popped when the returned guard is ended or dropped.
+    ///
+    /// Use [`ErrorScopeGuard::end`] to retrieve the captured error. Dropping the guard
+    /// instead pops the scope without waiting for it, discarding any captured error,
+    /// which keeps scopes balanced on early returns.
+    pub fn error_scope(&self, filter: ErrorFilter) -> ErrorScopeGuard {
+        self.push_error_scope(filter);
+        ErrorScopeGuard {
+            device: self,
+            ended: false,
+        }
+    }
+
     /// Starts frame capture.
     pub fn start_capture(&self) {
         Context::device_start_capture(&*self.context, &self.id)
@@ -1677,12 +2279,78 @@ impl Device {
 
 impl Drop for Device {
     fn drop(&mut self) {
+        self.shared.release();
         if !thread::panicking() {
             self.context.device_drop(&self.id);
         }
     }
 }
 
+/// Error scope pushed by [`Device::error_scope`].
+///
+/// The scope is popped when the guard is [ended](ErrorScopeGuard::end) or dropped.
+#[derive(Debug)]
+pub struct ErrorScopeGuard<'a> {
+    device: &'a Device,
+    ended: bool,
+}
+
+impl ErrorScopeGuard<'_> {
+    /// Pop the error scope, resolving to the first error it captured, if any.
+    pub fn end(mut self) -> impl Future<Output = Option<Error>> + Send {
+        self.ended = true;
+        self.device.pop_error_scope()
+    }
+}
+
+impl Drop for ErrorScopeGuard<'_> {
+    fn drop(&mut self) {
+        if !self.ended && !thread::panicking() {
+            // Popping happens immediately, the result is not needed
+            drop(self.device.pop_error_scope());
+        }
+    }
+}
+
+/// Debug group pushed by [`RenderPass::debug_group`], [`ComputePass::debug_group`] or
+/// [`CommandEncoder::debug_group`].
+///
+/// Dereferences to the pass or encoder, and pops the group when dropped.
+pub struct DebugGroupGuard<'p, P> {
+    pass: &'p mut P,
+    pop: fn(&mut P),
+}
+
+impl<P> std::ops::Deref for DebugGroupGuard<'_, P> {
+    type Target = P;
+
+    fn deref(&self) -> &P {
+        self.pass
+    }
+}
+
+impl<P> std::ops::DerefMut for DebugGroupGuard<'_, P> {
+    fn deref_mut(&mut self) -> &mut P {
+        self.pass
+    }
+}
+
+impl<P> Drop for DebugGroupGuard<'_, P> {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            (self.pop)(self.pass);
+        }
+    }
+}
+
+impl<P: Debug> Debug for DebugGroupGuard<'_, P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DebugGroupGuard")
+            .field("pass", &self.pass)
+            .finish()
+    }
+}
+
 /// Requesting a device failed.
 #[derive(Clone, PartialEq, Eq, Debug)]
 pub struct RequestDeviceError;
@@ -1695,25 +2363,377 @@ impl Display for RequestDeviceError {
 
 impl error::Error for RequestDeviceError {}
 
-/// Error occurred when trying to async map a buffer.
+/// The device did not complete its work before the timeout given to
+/// [`Device::poll_timeout`] elapsed.
 #[derive(Clone, PartialEq, Eq, Debug)]
-pub struct BufferAsyncError;
+pub struct PollTimeout;
 
-impl Display for BufferAsyncError {
+impl Display for PollTimeout {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Error occurred when trying to async map a buffer")
+        write!(f, "Timed out waiting for the device to complete its work")
     }
 }
 
-impl error::Error for BufferAsyncError {}
+impl error::Error for PollTimeout {}
 
-/// Type of buffer mapping.
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum MapMode {
-    /// Map only for reading
-    Read,
-    /// Map only for writing
-    Write,
+/// Invalid buffer to buffer copy, rejected by [`CommandEncoder::copy_buffer_to_buffer_checked`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum CopyError {
+    /// An offset or the copy size is not a multiple of [`COPY_BUFFER_ALIGNMENT`].
+    Misaligned {
+        /// Which value is misaligned: `"source offset"`, `"destination offset"` or `"size"`.
+        what: &'static str,
+        /// The misaligned value.
+        value: BufferAddress,
+    },
+    /// The copy overruns one of the buffers.
+    OutOfBounds {
+        /// The buffer overrun: `"source"` or `"destination"`.
+        buffer: &'static str,
+        /// Range of the buffer the copy would access.
+        range: Range<BufferAddress>,
+        /// Size of the buffer.
+        size: BufferAddress,
+    },
+    /// The source and destination are the same buffer, and their ranges overlap.
+    Overlap {
+        /// Range copied from.
+        source: Range<BufferAddress>,
+        /// Range copied to.
+        destination: Range<BufferAddress>,
+    },
+}
+
+impl Display for CopyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Misaligned { what, value } => write!(
+                f,
+                "Copy {} {} is not a multiple of {}",
+                what, value, COPY_BUFFER_ALIGNMENT
+            ),
+            Self::OutOfBounds {
+                buffer,
+                range,
+                size,
+            } => write!(
+                f,
+                "Copy of {}..{} overruns the {} buffer of size {}",
+                range.start, range.end, buffer, size
+            ),
+            Self::Overlap {
+                source,
+                destination,
+            } => write!(
+                f,
+                "Copy from {}..{} to {}..{} overlaps within the same buffer",
+                source.start, source.end, destination.start, destination.end
+            ),
+        }
+    }
+}
+
+impl error::Error for CopyError {}
+
+/// Error occurred when trying to async map a buffer.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BufferAsyncError;
+
+impl Display for BufferAsyncError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Error occurred when trying to async map a buffer")
+    }
+}
+
+impl error::Error for BufferAsyncError {}
+
+/// Type of buffer mapping.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MapMode {
+    /// Map only for reading
+    Read,
+    /// Map only for writing
+    Write,
+}
+
+/// Layout of tightly packed texel data of `size` in `format`, for
+/// [`Queue::write_texture_packed`], and the number of bytes it spans.
+fn packed_data_layout(format: TextureFormat, size: Extent3d) -> (ImageDataLayout, usize) {
+    let info = format.describe();
+    let block_width = info.block_dimensions.0 as u32;
+    let block_height = info.block_dimensions.1 as u32;
+    let width_blocks = (size.width + block_width - 1) / block_width;
+    let height_blocks = (size.height + block_height - 1) / block_height;
+    let bytes_per_row = width_blocks * info.block_size as u32;
+
+    let layout = ImageDataLayout {
+        offset: 0,
+        bytes_per_row: NonZeroU32::new(bytes_per_row),
+        // Rows per image are counted in texels of the physical size.
+        rows_per_image: NonZeroU32::new(height_blocks * block_height),
+    };
+    let len = bytes_per_row as usize * height_blocks as usize * size.depth_or_array_layers as usize;
+    (layout, len)
+}
+
+/// Calls `done` until it returns `true`, sleeping [`POLL_INTERVAL`] in between, or until
+/// `timeout` elapsed.
+#[cfg(not(target_arch = "wasm32"))]
+fn poll_until(
+    timeout: std::time::Duration,
+    mut done: impl FnMut() -> bool,
+) -> Result<(), PollTimeout> {
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        if done() {
+            return Ok(());
+        }
+        if std::time::Instant::now() >= deadline {
+            return Err(PollTimeout);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Position of an adapter of `device_type` in the order [`Instance::find_adapter`]
+/// prefers adapters in, lower is better.
+#[cfg(not(target_arch = "wasm32"))]
+fn adapter_rank(device_type: DeviceType, power_preference: PowerPreference) -> u8 {
+    match (device_type, power_preference) {
+        (DeviceType::DiscreteGpu, PowerPreference::HighPerformance)
+        | (DeviceType::IntegratedGpu, PowerPreference::LowPower) => 0,
+        (DeviceType::DiscreteGpu, _) | (DeviceType::IntegratedGpu, _) => 1,
+        (DeviceType::VirtualGpu, _) => 2,
+        (DeviceType::Cpu, _) => 3,
+        (DeviceType::Other, _) => 4,
+    }
+}
+
+/// Clamps a scissor rect to a `target_width` by `target_height` target, returning `None`
+/// if nothing of it is left.
+fn clamp_scissor_rect(
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+    target_width: u32,
+    target_height: u32,
+) -> Option<(u32, u32, u32, u32)> {
+    let right = x.saturating_add(width).min(target_width);
+    let bottom = y.saturating_add(height).min(target_height);
+    if x >= right || y >= bottom {
+        return None;
+    }
+    Some((x, y, right - x, bottom - y))
+}
+
+/// Number of work groups of `workgroup_size` needed to cover `total` invocations.
+fn workgroup_count(total: u32, workgroup_size: u32) -> u32 {
+    debug_assert_ne!(workgroup_size, 0, "Work group size can't be zero");
+    total / workgroup_size + (total % workgroup_size != 0) as u32
+}
+
+/// Checks a copy of `size` bytes between `(offset, buffer size)` pairs, for
+/// [`CommandEncoder::copy_buffer_to_buffer_checked`].
+fn check_buffer_copy(
+    (source_offset, source_size): (BufferAddress, BufferAddress),
+    (destination_offset, destination_size): (BufferAddress, BufferAddress),
+    size: BufferAddress,
+    same_buffer: bool,
+) -> Result<(), CopyError> {
+    for &(what, value) in &[
+        ("source offset", source_offset),
+        ("destination offset", destination_offset),
+        ("size", size),
+    ] {
+        if value % COPY_BUFFER_ALIGNMENT != 0 {
+            return Err(CopyError::Misaligned { what, value });
+        }
+    }
+    let range = |buffer, offset: BufferAddress, buffer_size| match offset.checked_add(size) {
+        Some(end) if end <= buffer_size => Ok(offset..end),
+        end => Err(CopyError::OutOfBounds {
+            buffer,
+            range: offset..end.unwrap_or(BufferAddress::MAX),
+            size: buffer_size,
+        }),
+    };
+    let source = range("source", source_offset, source_size)?;
+    let destination = range("destination", destination_offset, destination_size)?;
+    if same_buffer && source.start < destination.end && destination.start < source.end {
+        return Err(CopyError::Overlap {
+            source,
+            destination,
+        });
+    }
+    Ok(())
+}
+
+/// Mip levels and array layers selected by `range` out of a texture's, checking they are
+/// in bounds.
+fn subresource_ranges(
+    range: &ImageSubresourceRange,
+    mip_level_count: u32,
+    array_layer_count: u32,
+) -> (Range<u32>, Range<u32>) {
+    let resolve = |what, base: u32, count: Option<NonZeroU32>, total: u32| {
+        let end = match count {
+            Some(count) => base.checked_add(count.get()),
+            None => Some(total.max(base)),
+        };
+        match end {
+            Some(end) if end <= total => base..end,
+            _ => panic!(
+                "Clear of {} {}..{:?} is out of bounds of {}",
+                what,
+                base,
+                count.map(|count| base as u64 + count.get() as u64),
+                total
+            ),
+        }
+    };
+    (
+        resolve(
+            "mip levels",
+            range.base_mip_level,
+            range.mip_level_count,
+            mip_level_count,
+        ),
+        resolve(
+            "array layers",
+            range.base_array_layer,
+            range.array_layer_count,
+            array_layer_count,
+        ),
+    )
+}
+
+/// Copies of zeroes recorded by [`CommandEncoder::clear_texture`] for the given mip levels
+/// and array layers, each reading at most `source_size` bytes from the start of the source.
+///
+/// Every image is copied at once if it fits, or in strips of rows otherwise. The layers of a
+/// 3D texture are the depth slices of each mip level.
+fn clear_texture_copies(
+    format: TextureFormat,
+    size: Extent3d,
+    dimension: TextureDimension,
+    mip_levels: Range<u32>,
+    array_layers: Range<u32>,
+    source_size: BufferAddress,
+) -> Vec<(u32, Origin3d, ImageDataLayout, Extent3d)> {
+    use util::Extent3dExt;
+
+    assert!(
+        !util::is_depth_format(format),
+        "Can't clear {:?} texture by copying into it",
+        format
+    );
+    let info = format.describe();
+    let (block_width, block_height) = (
+        info.block_dimensions.0 as u32,
+        info.block_dimensions.1 as u32,
+    );
+    let block_size = info.block_size as BufferAddress;
+    let align = |bytes: BufferAddress| {
+        let mask = COPY_BYTES_PER_ROW_ALIGNMENT as BufferAddress - 1;
+        (bytes + mask) & !mask
+    };
+    let mut copies = Vec::new();
+    for mip_level in mip_levels {
+        let mip_size = size
+            .mip_level_size(mip_level, dimension)
+            .physical_size(format);
+        let layers = match dimension {
+            TextureDimension::D3 => 0..mip_size.depth_or_array_layers,
+            _ => array_layers.clone(),
+        };
+        let width_blocks = mip_size.width / block_width;
+        let height_blocks = mip_size.height / block_height;
+        // Rows wider than the source are split into columns
+        let strip_blocks = (width_blocks as BufferAddress).min(source_size / block_size) as u32;
+        assert!(strip_blocks > 0, "Clear source is smaller than a block");
+        let mut x = 0;
+        while x < width_blocks {
+            let width = strip_blocks.min(width_blocks - x);
+            let bytes_per_row = align(width as BufferAddress * block_size);
+            let rows = (source_size / bytes_per_row).min(u32::MAX as BufferAddress) as u32;
+            let mut copy = |y: u32, z: u32, height: u32, depth: u32| {
+                copies.push((
+                    mip_level,
+                    Origin3d {
+                        x: x * block_width,
+                        y: y * block_height,
+                        z,
+                    },
+                    ImageDataLayout {
+                        offset: 0,
+                        bytes_per_row: NonZeroU32::new(bytes_per_row as u32),
+                        rows_per_image: NonZeroU32::new(height * block_height),
+                    },
+                    Extent3d {
+                        width: width * block_width,
+                        height: height * block_height,
+                        depth_or_array_layers: depth,
+                    },
+                ))
+            };
+            if rows >= height_blocks {
+                let layers_per_copy = rows / height_blocks;
+                let mut z = layers.start;
+                while z < layers.end {
+                    let depth = layers_per_copy.min(layers.end - z);
+                    copy(0, z, height_blocks, depth);
+                    z += depth;
+                }
+            } else {
+                for z in layers.clone() {
+                    let mut y = 0;
+                    while y < height_blocks {
+                        let height = rows.min(height_blocks - y);
+                        copy(y, z, height, 1);
+                        y += height;
+                    }
+                }
+            }
+            x += width;
+        }
+    }
+    copies
+}
+
+/// Size of the range cleared by [`CommandEncoder::clear_buffer`], checking its alignment
+/// and bounds.
+fn clear_buffer_size(
+    total_size: BufferAddress,
+    offset: BufferAddress,
+    size: Option<BufferSize>,
+) -> BufferAddress {
+    assert_eq!(
+        offset % COPY_BUFFER_ALIGNMENT,
+        0,
+        "Clear offset {} is not a multiple of {}",
+        offset,
+        COPY_BUFFER_ALIGNMENT
+    );
+    let size = match size {
+        Some(size) => size.get(),
+        None => total_size.saturating_sub(offset),
+    };
+    assert_eq!(
+        size % COPY_BUFFER_ALIGNMENT,
+        0,
+        "Clear size {} is not a multiple of {}",
+        size,
+        COPY_BUFFER_ALIGNMENT
+    );
+    assert!(
+        offset + size <= total_size,
+        "Clear of {}..{} overruns the buffer of size {}",
+        offset,
+        offset + size,
+        total_size
+    );
+    size
 }
 
 fn range_to_offset_size<S: RangeBounds<BufferAddress>>(
@@ -1736,7 +2756,288 @@ fn range_to_offset_size<S: RangeBounds<BufferAddress>>(
 
 #[cfg(test)]
 mod tests {
-    use crate::BufferSize;
+    use crate::{AddressMode, BufferSize, FilterMode, SamplerDescriptor};
+
+    #[test]
+    fn sampler_presets() {
+        let nearest = SamplerDescriptor::nearest_clamp();
+        assert_eq!(nearest.mag_filter, FilterMode::Nearest);
+        assert_eq!(nearest.mipmap_filter, FilterMode::Nearest);
+        assert_eq!(nearest.address_mode_u, AddressMode::ClampToEdge);
+        assert_eq!(nearest.address_mode_w, AddressMode::ClampToEdge);
+
+        let linear = SamplerDescriptor::linear_clamp();
+        assert_eq!(linear.mag_filter, FilterMode::Linear);
+        assert_eq!(linear.address_mode_v, AddressMode::ClampToEdge);
+
+        const REPEAT: SamplerDescriptor = SamplerDescriptor::linear_repeat();
+        assert_eq!(REPEAT.label, None);
+        assert_eq!(REPEAT.min_filter, FilterMode::Linear);
+        assert_eq!(REPEAT.address_mode_u, AddressMode::Repeat);
+        assert_eq!(REPEAT.lod_max_clamp, std::f32::MAX);
+        assert_eq!(REPEAT.anisotropy_clamp, None);
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn poll_until_times_out() {
+        use crate::{poll_until, PollTimeout};
+        use std::time::{Duration, Instant};
+
+        // Work that was never submitted doesn't complete
+        let start = Instant::now();
+        assert_eq!(
+            poll_until(Duration::from_millis(20), || false),
+            Err(PollTimeout)
+        );
+        assert!(start.elapsed() >= Duration::from_millis(20));
+
+        let mut polls = 0;
+        let result = poll_until(Duration::from_secs(10), || {
+            polls += 1;
+            polls == 3
+        });
+        assert_eq!((result, polls), (Ok(()), 3));
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn adapter_rank_follows_power_preference() {
+        use crate::{adapter_rank, DeviceType, PowerPreference};
+
+        let order = |power_preference| {
+            let mut types = [
+                DeviceType::Other,
+                DeviceType::Cpu,
+                DeviceType::VirtualGpu,
+                DeviceType::IntegratedGpu,
+                DeviceType::DiscreteGpu,
+            ];
+            types.sort_by_key(|&ty| adapter_rank(ty, power_preference));
+            types
+        };
+        assert_eq!(
+            order(PowerPreference::HighPerformance),
+            [
+                DeviceType::DiscreteGpu,
+                DeviceType::IntegratedGpu,
+                DeviceType::VirtualGpu,
+                DeviceType::Cpu,
+                DeviceType::Other,
+            ]
+        );
+        assert_eq!(
+            order(PowerPreference::LowPower),
+            [
+                DeviceType::IntegratedGpu,
+                DeviceType::DiscreteGpu,
+                DeviceType::VirtualGpu,
+                DeviceType::Cpu,
+                DeviceType::Other,
+            ]
+        );
+    }
+
+    #[test]
+    fn packed_data_layout_rgba() {
+        use crate::{Extent3d, TextureFormat};
+        use std::num::NonZeroU32;
+
+        let size = Extent3d {
+            width: 3,
+            height: 2,
+            depth_or_array_layers: 1,
+        };
+        let (layout, len) = crate::packed_data_layout(TextureFormat::Rgba8Unorm, size);
+        assert_eq!(layout.offset, 0);
+        assert_eq!(layout.bytes_per_row, NonZeroU32::new(12));
+        assert_eq!(layout.rows_per_image, NonZeroU32::new(2));
+        assert_eq!(len, 24);
+    }
+
+    #[test]
+    fn packed_data_layout_compressed_layers() {
+        use crate::{Extent3d, TextureFormat};
+        use std::num::NonZeroU32;
+
+        let size = Extent3d {
+            width: 10,
+            height: 6,
+            depth_or_array_layers: 2,
+        };
+        let (layout, len) = crate::packed_data_layout(TextureFormat::Bc1RgbaUnorm, size);
+        // 3x2 blocks of 8 bytes, per layer
+        assert_eq!(layout.bytes_per_row, NonZeroU32::new(24));
+        assert_eq!(layout.rows_per_image, NonZeroU32::new(8));
+        assert_eq!(len, 96);
+    }
+
+    #[test]
+    fn texture_view_descriptor_subresources() {
+        use crate::{TextureViewDescriptor, TextureViewDimension};
+        use std::num::NonZeroU32;
+
+        let mip = TextureViewDescriptor::mip(3);
+        assert_eq!(mip.base_mip_level, 3);
+        assert_eq!(mip.mip_level_count, NonZeroU32::new(1));
+        assert_eq!(mip.base_array_layer, 0);
+        assert_eq!(mip.array_layer_count, None);
+        assert_eq!(mip.dimension, None);
+
+        let layer = TextureViewDescriptor::layer(5);
+        assert_eq!(layer.base_mip_level, 0);
+        assert_eq!(layer.mip_level_count, None);
+        assert_eq!(layer.base_array_layer, 5);
+        assert_eq!(layer.array_layer_count, NonZeroU32::new(1));
+        assert_eq!(layer.dimension, Some(TextureViewDimension::D2));
+
+        let both = TextureViewDescriptor::mip_layer(2, 4);
+        assert_eq!(both.base_mip_level, 2);
+        assert_eq!(both.mip_level_count, NonZeroU32::new(1));
+        assert_eq!(both.base_array_layer, 4);
+        assert_eq!(both.array_layer_count, NonZeroU32::new(1));
+        assert_eq!(both.dimension, Some(TextureViewDimension::D2));
+    }
+
+    #[test]
+    fn map_context_tracks_mode() {
+        use crate::{MapContext, MapMode};
+        use std::sync::atomic::Ordering;
+
+        let mut mc = MapContext::new(16);
+        assert_eq!(mc.mode, None);
+        let mapped = mc.map(4..12, MapMode::Read);
+        assert_eq!(mc.initial_range, 4..12);
+        assert_eq!(mc.mode, Some(MapMode::Read));
+        // Mapped once the backend says so, not when the mapping is requested
+        assert!(!mc.is_mapped());
+        mapped.store(true, Ordering::Release);
+        assert!(mc.is_mapped());
+        mc.reset();
+        assert_eq!(mc.initial_range, 0..0);
+        assert_eq!(mc.mode, None);
+        assert!(!mc.is_mapped());
+        // A late result of the reset mapping doesn't count for the next one
+        mc.map(0..16, MapMode::Write);
+        mapped.store(true, Ordering::Release);
+        assert!(!mc.is_mapped());
+    }
+
+    #[test]
+    fn buffer_copy_checks() {
+        use crate::{check_buffer_copy, CopyError};
+
+        assert_eq!(check_buffer_copy((0, 256), (64, 128), 64, false), Ok(()));
+        assert_eq!(check_buffer_copy((0, 256), (64, 256), 64, true), Ok(()));
+        assert_eq!(
+            check_buffer_copy((2, 256), (0, 256), 64, false),
+            Err(CopyError::Misaligned {
+                what: "source offset",
+                value: 2,
+            })
+        );
+        assert_eq!(
+            check_buffer_copy((0, 256), (0, 256), 6, false),
+            Err(CopyError::Misaligned {
+                what: "size",
+                value: 6,
+            })
+        );
+        assert_eq!(
+            check_buffer_copy((0, 256), (96, 128), 64, false),
+            Err(CopyError::OutOfBounds {
+                buffer: "destination",
+                range: 96..160,
+                size: 128,
+            })
+        );
+        assert_eq!(
+            check_buffer_copy((0, 256), (32, 256), 64, true),
+            Err(CopyError::Overlap {
+                source: 0..64,
+                destination: 32..96,
+            })
+        );
+    }
+
+    #[test]
+    fn clamp_scissor_rect_in_bounds() {
+        assert_eq!(
+            crate::clamp_scissor_rect(10, 20, 30, 40, 800, 600),
+            Some((10, 20, 30, 40))
+        );
+        assert_eq!(
+            crate::clamp_scissor_rect(0, 0, 800, 600, 800, 600),
+            Some((0, 0, 800, 600))
+        );
+    }
+
+    #[test]
+    fn clamp_scissor_rect_overflowing() {
+        assert_eq!(
+            crate::clamp_scissor_rect(700, 500, 200, 200, 800, 600),
+            Some((700, 500, 100, 100))
+        );
+        assert_eq!(
+            crate::clamp_scissor_rect(10, 10, u32::MAX, u32::MAX, 800, 600),
+            Some((10, 10, 790, 590))
+        );
+    }
+
+    #[test]
+    fn clamp_scissor_rect_out_of_bounds() {
+        assert_eq!(crate::clamp_scissor_rect(800, 0, 10, 10, 800, 600), None);
+        assert_eq!(crate::clamp_scissor_rect(0, 900, 10, 10, 800, 600), None);
+        assert_eq!(crate::clamp_scissor_rect(10, 10, 0, 10, 800, 600), None);
+    }
+
+    #[test]
+    fn workgroup_count_rounds_up() {
+        assert_eq!(crate::workgroup_count(100, 64), 2);
+        assert_eq!(crate::workgroup_count(1, 64), 1);
+        assert_eq!(crate::workgroup_count(u32::MAX, 2), u32::MAX / 2 + 1);
+    }
+
+    #[test]
+    fn workgroup_count_exact_multiples() {
+        assert_eq!(crate::workgroup_count(128, 64), 2);
+        assert_eq!(crate::workgroup_count(0, 64), 0);
+        assert_eq!(crate::workgroup_count(7, 1), 7);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "can't be zero")]
+    fn workgroup_count_panics_for_zero_size() {
+        crate::workgroup_count(100, 0);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn mapped_cast_f32() {
+        let floats = [1.0f32, 2.5, -4.0];
+        let bytes: &[u8] = bytemuck::cast_slice(&floats);
+        crate::check_mapped_cast::<f32>(bytes);
+        assert_eq!(bytemuck::cast_slice::<u8, f32>(bytes), floats);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    #[should_panic(expected = "not aligned to 4 bytes")]
+    fn mapped_cast_panics_when_misaligned() {
+        let floats = [0.0f32; 3];
+        let bytes: &[u8] = bytemuck::cast_slice(&floats);
+        crate::check_mapped_cast::<f32>(&bytes[1..5]);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    #[should_panic(expected = "not a multiple of the size of f32")]
+    fn mapped_cast_panics_for_partial_element() {
+        let floats = [0.0f32; 3];
+        let bytes: &[u8] = bytemuck::cast_slice(&floats);
+        crate::check_mapped_cast::<f32>(&bytes[..6]);
+    }
 
     #[test]
     fn range_to_offset_size_works() {
@@ -1759,6 +3060,189 @@ mod tests {
     fn range_to_offset_size_panics_for_unbounded_empty_range() {
         crate::range_to_offset_size(..0);
     }
+
+    #[test]
+    fn clear_buffer_size_to_end() {
+        assert_eq!(crate::clear_buffer_size(256, 0, None), 256);
+        assert_eq!(crate::clear_buffer_size(256, 64, None), 192);
+        assert_eq!(crate::clear_buffer_size(256, 256, None), 0);
+        assert_eq!(crate::clear_buffer_size(256, 8, BufferSize::new(16)), 16);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clear_buffer_size_panics_for_unaligned_offset() {
+        crate::clear_buffer_size(256, 2, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn clear_buffer_size_panics_for_overrun() {
+        crate::clear_buffer_size(256, 252, BufferSize::new(8));
+    }
+
+    #[test]
+    fn subresource_ranges_resolve_counts() {
+        use crate::{ImageSubresourceRange, TextureAspect};
+        use std::num::NonZeroU32;
+
+        let mut range = ImageSubresourceRange {
+            aspect: TextureAspect::All,
+            base_mip_level: 0,
+            mip_level_count: None,
+            base_array_layer: 0,
+            array_layer_count: None,
+        };
+        assert_eq!(crate::subresource_ranges(&range, 4, 6), (0..4, 0..6));
+        range.base_mip_level = 1;
+        range.mip_level_count = NonZeroU32::new(2);
+        range.base_array_layer = 5;
+        assert_eq!(crate::subresource_ranges(&range, 4, 6), (1..3, 5..6));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn subresource_ranges_panics_for_missing_layers() {
+        let range = crate::ImageSubresourceRange {
+            aspect: crate::TextureAspect::All,
+            base_mip_level: 0,
+            mip_level_count: None,
+            base_array_layer: 2,
+            array_layer_count: std::num::NonZeroU32::new(2),
+        };
+        crate::subresource_ranges(&range, 1, 3);
+    }
+
+    #[test]
+    fn clear_texture_copies_per_mip() {
+        use crate::{Extent3d, TextureDimension, TextureFormat};
+        use std::num::NonZeroU32;
+
+        let size = Extent3d {
+            width: 16,
+            height: 8,
+            depth_or_array_layers: 4,
+        };
+        let copies = crate::clear_texture_copies(
+            TextureFormat::Rgba8Unorm,
+            size,
+            TextureDimension::D2,
+            1..3,
+            1..3,
+            1 << 20,
+        );
+        let extents: Vec<_> = copies
+            .iter()
+            .map(|&(mip, origin, _, size)| {
+                (
+                    mip,
+                    origin.z,
+                    size.width,
+                    size.height,
+                    size.depth_or_array_layers,
+                )
+            })
+            .collect();
+        // Only the selected mips and layers, both layers at once
+        assert_eq!(extents, [(1, 1, 8, 4, 2), (2, 1, 4, 2, 2)]);
+        assert_eq!(copies[0].2.bytes_per_row, NonZeroU32::new(256));
+        assert_eq!(copies[0].2.rows_per_image, NonZeroU32::new(4));
+
+        let size = Extent3d {
+            width: 5,
+            height: 5,
+            depth_or_array_layers: 1,
+        };
+        let copies = crate::clear_texture_copies(
+            TextureFormat::Bc1RgbaUnorm,
+            size,
+            TextureDimension::D2,
+            0..2,
+            0..1,
+            1 << 20,
+        );
+        // Copies cover whole blocks
+        assert_eq!(copies[0].3.width, 8);
+        assert_eq!(copies[1].3.width, 4);
+        assert_eq!(copies[1].3.height, 4);
+    }
+
+    #[test]
+    fn clear_texture_copies_split_to_source() {
+        use crate::{Extent3d, TextureDimension, TextureFormat};
+
+        let size = Extent3d {
+            width: 128,
+            height: 8,
+            depth_or_array_layers: 2,
+        };
+        // 512 bytes per row, so a 1024 byte source holds two rows
+        let copies = crate::clear_texture_copies(
+            TextureFormat::Rgba8Unorm,
+            size,
+            TextureDimension::D2,
+            0..1,
+            0..2,
+            1024,
+        );
+        assert_eq!(copies.len(), 8);
+        assert!(copies.iter().all(|&(_, _, _, size)| size.height == 2));
+        let (_, origin, _, _) = copies[5];
+        assert_eq!((origin.y, origin.z), (2, 1));
+
+        // Rows wider than the source are split into columns
+        let copies = crate::clear_texture_copies(
+            TextureFormat::Rgba8Unorm,
+            size,
+            TextureDimension::D2,
+            0..1,
+            0..1,
+            256,
+        );
+        assert_eq!(copies.len(), 16);
+        let (_, origin, _, size) = copies[8];
+        assert_eq!(
+            (origin.x, origin.y, size.width, size.height),
+            (64, 0, 64, 1)
+        );
+
+        // Depth slices of a 3D texture are its layers
+        let copies = crate::clear_texture_copies(
+            TextureFormat::Rgba8Unorm,
+            Extent3d {
+                width: 4,
+                height: 4,
+                depth_or_array_layers: 4,
+            },
+            TextureDimension::D3,
+            1..2,
+            0..1,
+            1 << 20,
+        );
+        let (_, origin, _, size) = copies[0];
+        assert_eq!(
+            (copies.len(), origin.z, size.depth_or_array_layers),
+            (1, 0, 2)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Can't clear")]
+    fn clear_texture_copies_panics_for_depth() {
+        let size = crate::Extent3d {
+            width: 4,
+            height: 4,
+            depth_or_array_layers: 1,
+        };
+        crate::clear_texture_copies(
+            crate::TextureFormat::Depth32Float,
+            size,
+            crate::TextureDimension::D2,
+            0..1,
+            0..1,
+            1 << 20,
+        );
+    }
 }
 
 trait BufferMappedRangeSlice {
@@ -1820,6 +3304,57 @@ impl AsMut<[u8]> for BufferViewMut<'_> {
     }
 }
 
+#[cfg(feature = "bytemuck")]
+impl BufferView<'_> {
+    /// Views the mapped bytes as a slice of `T`.
+    ///
+    /// # Panics
+    ///
+    /// - The mapping is not aligned to `T`.
+    /// - The mapping size is not a multiple of the size of `T`.
+    pub fn as_slice<T: bytemuck::Pod>(&self) -> &[T] {
+        check_mapped_cast::<T>(self.data.slice());
+        bytemuck::cast_slice(self.data.slice())
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+impl BufferViewMut<'_> {
+    /// Views the mapped bytes as a mutable slice of `T`.
+    ///
+    /// # Panics
+    ///
+    /// - The mapping is not aligned to `T`.
+    /// - The mapping size is not a multiple of the size of `T`.
+    pub fn as_slice_mut<T: bytemuck::Pod>(&mut self) -> &mut [T] {
+        check_mapped_cast::<T>(self.data.slice());
+        bytemuck::cast_slice_mut(self.data.slice_mut())
+    }
+}
+
+/// Checks that `bytes` can be viewed as a slice of `T`, panicking with a helpful message
+/// otherwise.
+#[cfg(feature = "bytemuck")]
+fn check_mapped_cast<T>(bytes: &[u8]) {
+    let align = std::mem::align_of::<T>();
+    let size = std::mem::size_of::<T>();
+    assert_eq!(
+        bytes.as_ptr() as usize % align,
+        0,
+        "Mapped range is not aligned to {} bytes, required by {}",
+        align,
+        std::any::type_name::<T>()
+    );
+    assert_eq!(
+        bytes.len() % size,
+        0,
+        "Mapped range of {} bytes is not a multiple of the size of {} ({} bytes)",
+        bytes.len(),
+        std::any::type_name::<T>(),
+        size
+    );
+}
+
 impl Drop for BufferView<'_> {
     fn drop(&mut self) {
         self.slice
@@ -1840,6 +3375,67 @@ impl Drop for BufferViewMut<'_> {
     }
 }
 
+/// Write only view into the staging buffer of a [`Queue::write_buffer_with`] call.
+///
+/// The data is copied to the buffer when the view is dropped.
+#[derive(Debug)]
+pub struct QueueWriteBufferView<'a> {
+    queue: &'a Queue,
+    buffer: &'a Buffer,
+    offset: BufferAddress,
+    staging: Buffer,
+    data: Option<BufferMappedRange>,
+}
+
+impl std::ops::Deref for QueueWriteBufferView<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.data.as_ref().unwrap().slice()
+    }
+}
+
+impl std::ops::DerefMut for QueueWriteBufferView<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.data.as_mut().unwrap().slice_mut()
+    }
+}
+
+impl AsMut<[u8]> for QueueWriteBufferView<'_> {
+    fn as_mut(&mut self) -> &mut [u8] {
+        self
+    }
+}
+
+impl Drop for QueueWriteBufferView<'_> {
+    fn drop(&mut self) {
+        // The mapping has to go before the staging buffer is unmapped
+        self.data.take();
+        if thread::panicking() {
+            return;
+        }
+        self.staging.unmap();
+        let mut encoder = self
+            .queue
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor {
+                label: Some("write_buffer_with"),
+            });
+        encoder.copy_buffer_to_buffer(
+            &self.staging,
+            0,
+            self.buffer,
+            self.offset,
+            self.staging.size,
+        );
+        self.queue
+            .device
+            .staged_writes
+            .lock()
+            .push(encoder.finish());
+    }
+}
+
 impl Buffer {
     /// Return the binding view of the entire buffer.
     pub fn as_entire_binding(&self) -> BindingResource {
@@ -1876,6 +3472,16 @@ impl Buffer {
     pub fn destroy(&self) {
         Context::buffer_destroy(&*self.context, &self.id);
     }
+
+    /// Size of the buffer in bytes, as given at creation.
+    pub fn size(&self) -> BufferAddress {
+        self.size
+    }
+
+    /// Allowed usages of the buffer, as given at creation.
+    pub fn usage(&self) -> BufferUsage {
+        self.usage
+    }
 }
 
 impl<'a> BufferSlice<'a> {
@@ -1889,6 +3495,21 @@ impl<'a> BufferSlice<'a> {
     ///
     /// It's expected that wgpu will eventually supply its own event loop infrastructure that will be easy to integrate
     /// into other event loops, like winit's.
+    ///
+    /// The returned future does not borrow the slice, so it can be awaited while the slice is
+    /// used for [`get_mapped_range`](BufferSlice::get_mapped_range) afterwards:
+    ///
+    /// ```no_run
+    /// # async fn example(device: &wgpu::Device, buffer: &wgpu::Buffer) {
+    /// let slice = buffer.slice(..);
+    /// let mapping = slice.map_async(wgpu::MapMode::Read);
+    /// device.poll(wgpu::Maintain::Wait);
+    /// if mapping.await.is_ok() {
+    ///     let data = slice.get_mapped_range().to_vec();
+    ///     buffer.unmap();
+    /// }
+    /// # }
+    /// ```
     pub fn map_async(
         &self,
         mode: MapMode,
@@ -1904,16 +3525,56 @@ impl<'a> BufferSlice<'a> {
             Some(s) => self.offset + s.get(),
             None => mc.total_size,
         };
-        mc.initial_range = self.offset..end;
+        let mapped = mc.map(self.offset..end, mode);
 
         Context::buffer_map_async(
             &*self.buffer.context,
             &self.buffer.id,
             mode,
             self.offset..end,
+            mapped,
         )
     }
 
+    /// Accesses the mapped slice, unmapping the whole buffer once the guard is dropped.
+    ///
+    /// The guard derefs to the mapped bytes, and can be written to if the buffer was mapped
+    /// with [`MapMode::Write`] or [`mapped_at_creation`](BufferDescriptor::mapped_at_creation).
+    /// Other views into the mapping have to be dropped before the guard, as unmapping panics
+    /// while they are alive.
+    ///
+    /// ```no_run
+    /// # async fn example(device: &wgpu::Device, buffer: &wgpu::Buffer) {
+    /// let slice = buffer.slice(..);
+    /// let mapping = slice.map_async(wgpu::MapMode::Read);
+    /// device.poll(wgpu::Maintain::Wait);
+    /// if mapping.await.is_ok() {
+    ///     let data = slice.mapped_guard().to_vec();
+    ///     // The buffer is unmapped here, and can be mapped again
+    /// }
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the buffer isn't mapped yet, which it is only once the backend reported the
+    /// mapping of [`map_async`](BufferSlice::map_async) done.
+    pub fn mapped_guard(&self) -> MappedBufferGuard<'a> {
+        let writable = {
+            let mc = self.buffer.map_context.lock();
+            assert!(
+                mc.is_mapped(),
+                "Buffer {:?} is not mapped, map_async has to complete before accessing it",
+                self.buffer.id
+            );
+            mc.mode == Some(MapMode::Write)
+        };
+        MappedBufferGuard {
+            view: Some(self.get_mapped_range_mut()),
+            writable,
+        }
+    }
+
     /// Synchronously and immediately map a buffer for reading. If the buffer is not immediately mappable
     /// through [`BufferDescriptor::mapped_at_creation`] or [`BufferSlice::map_async`], will panic.
     pub fn get_mapped_range(&self) -> BufferView<'a> {
@@ -1943,6 +3604,46 @@ impl<'a> BufferSlice<'a> {
     }
 }
 
+/// Access to a mapped range of a [`Buffer`], unmapping the buffer when dropped.
+///
+/// Created with [`BufferSlice::mapped_guard`].
+#[derive(Debug)]
+pub struct MappedBufferGuard<'a> {
+    // Only taken when dropped, so the view is released before unmapping.
+    view: Option<BufferViewMut<'a>>,
+    writable: bool,
+}
+
+impl std::ops::Deref for MappedBufferGuard<'_> {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        self.view.as_ref().unwrap().data.slice()
+    }
+}
+
+impl std::ops::DerefMut for MappedBufferGuard<'_> {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        let view = self.view.as_mut().unwrap();
+        assert!(
+            self.writable,
+            "Attempting to write to a read mapping for buffer {:?}",
+            view.slice.buffer.id
+        );
+        view.data.slice_mut()
+    }
+}
+
+impl Drop for MappedBufferGuard<'_> {
+    fn drop(&mut self) {
+        if let Some(view) = self.view.take() {
+            let buffer = view.slice.buffer;
+            drop(view);
+            buffer.unmap();
+        }
+    }
+}
+
 impl Drop for Buffer {
     fn drop(&mut self) {
         if !thread::panicking() {
@@ -1961,10 +3662,67 @@ impl Texture {
         }
     }
 
+    /// Creates a view of the whole texture, with the default [`TextureViewDescriptor`].
+    pub fn default_view(&self) -> TextureView {
+        self.create_view(&TextureViewDescriptor::default())
+    }
+
+    /// Creates a labeled view of the whole texture, with the default [`TextureViewDescriptor`].
+    pub fn default_view_labeled(&self, label: &str) -> TextureView {
+        self.create_view(&TextureViewDescriptor {
+            label: Some(label),
+            ..TextureViewDescriptor::default()
+        })
+    }
+
     /// Destroy the associated native resources as soon as possible.
     pub fn destroy(&self) {
         Context::texture_destroy(&*self.context, &self.id);
     }
+
+    /// Size of the top mip level of the texture, as given at creation.
+    pub fn size(&self) -> Extent3d {
+        self.size
+    }
+
+    /// Number of mip levels of the texture, as given at creation.
+    pub fn mip_level_count(&self) -> u32 {
+        self.mip_level_count
+    }
+
+    /// Number of samples per texel of the texture, as given at creation.
+    pub fn sample_count(&self) -> u32 {
+        self.sample_count
+    }
+
+    /// Dimension of the texture, as given at creation.
+    pub fn dimension(&self) -> TextureDimension {
+        self.dimension
+    }
+
+    /// Format of the texture, as given at creation.
+    pub fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    /// Allowed usages of the texture, as given at creation.
+    pub fn usage(&self) -> TextureUsage {
+        self.usage
+    }
+
+    /// Make an `ImageCopyTexture` representing the whole texture, at mip level 0.
+    pub fn as_image_copy(&self) -> ImageCopyTexture {
+        self.as_image_copy_mip(0, Origin3d { x: 0, y: 0, z: 0 })
+    }
+
+    /// Make an `ImageCopyTexture` starting at `origin` of the given mip level.
+    pub fn as_image_copy_mip(&self, mip_level: u32, origin: Origin3d) -> ImageCopyTexture {
+        ImageCopyTexture {
+            texture: self,
+            mip_level,
+            origin,
+        }
+    }
 }
 
 impl Drop for Texture {
@@ -2020,6 +3778,86 @@ impl CommandEncoder {
         }
     }
 
+    /// Fills `size` bytes of `buffer` starting at `offset` with zeroes, or up to the end of
+    /// the buffer if `size` is `None`.
+    ///
+    /// There is no native clear command to record, so this copies from a zero-filled buffer
+    /// the device keeps for all its encoders, as many times as it takes. No feature is
+    /// needed, but `buffer` needs the [`COPY_DST`](BufferUsage::COPY_DST) usage.
+    ///
+    /// # Panics
+    ///
+    /// - `offset` or the cleared size not a multiple of [`COPY_BUFFER_ALIGNMENT`].
+    /// - Clear would overrun the buffer.
+    pub fn clear_buffer(
+        &mut self,
+        buffer: &Buffer,
+        offset: BufferAddress,
+        size: Option<BufferSize>,
+    ) {
+        let size = clear_buffer_size(buffer.size, offset, size);
+        if size == 0 {
+            return;
+        }
+        let zeroes = self.device.clear_source();
+        let mut cleared = 0;
+        while cleared < size {
+            let chunk = zeroes.size.min(size - cleared);
+            self.copy_buffer_to_buffer(&zeroes, 0, buffer, offset + cleared, chunk);
+            cleared += chunk;
+        }
+    }
+
+    /// Fills the mip levels and array layers of `texture` selected by `subresource` with
+    /// zeroes.
+    ///
+    /// Like [`clear_buffer`](CommandEncoder::clear_buffer), this copies from the zero-filled
+    /// buffer of the device, image by image or in strips of rows when an image is bigger
+    /// than it. `texture` needs the [`COPY_DST`](TextureUsage::COPY_DST) usage.
+    ///
+    /// # Panics
+    ///
+    /// - `texture` has a depth or depth-stencil format, which can't be copied into.
+    /// - `texture` is multisampled, which can't be copied into either.
+    /// - `subresource` selects an aspect other than [`TextureAspect::All`].
+    /// - `subresource` selects mip levels or array layers the texture doesn't have.
+    pub fn clear_texture(&mut self, texture: &Texture, subresource: &ImageSubresourceRange) {
+        assert_eq!(
+            texture.sample_count, 1,
+            "Can't clear multisampled texture by copying into it"
+        );
+        assert_eq!(
+            subresource.aspect,
+            TextureAspect::All,
+            "Can't clear a single aspect of a texture"
+        );
+        let array_layer_count = match texture.dimension {
+            TextureDimension::D3 => 1,
+            _ => texture.size.depth_or_array_layers,
+        };
+        let (mip_levels, array_layers) =
+            subresource_ranges(subresource, texture.mip_level_count, array_layer_count);
+        let zeroes = self.device.clear_source();
+        let copies = clear_texture_copies(
+            texture.format,
+            texture.size,
+            texture.dimension,
+            mip_levels,
+            array_layers,
+            zeroes.size,
+        );
+        for (mip_level, origin, layout, copy_size) in copies {
+            self.copy_buffer_to_texture(
+                ImageCopyBuffer {
+                    buffer: &zeroes,
+                    layout,
+                },
+                texture.as_image_copy_mip(mip_level, origin),
+                copy_size,
+            );
+        }
+    }
+
     /// Copy data from one buffer to another.
     ///
     /// # Panics
@@ -2045,6 +3883,36 @@ impl CommandEncoder {
         );
     }
 
+    /// Copy data from one buffer to another, validating the copy first.
+    ///
+    /// Unlike [`copy_buffer_to_buffer`](CommandEncoder::copy_buffer_to_buffer), the alignment
+    /// of the offsets and size, the bounds of both buffers and, when copying within a single
+    /// buffer, the overlap of both ranges are checked up front. Nothing is recorded if the
+    /// copy is invalid.
+    pub fn copy_buffer_to_buffer_checked(
+        &mut self,
+        source: &Buffer,
+        source_offset: BufferAddress,
+        destination: &Buffer,
+        destination_offset: BufferAddress,
+        copy_size: BufferAddress,
+    ) -> Result<(), CopyError> {
+        check_buffer_copy(
+            (source_offset, source.size()),
+            (destination_offset, destination.size()),
+            copy_size,
+            std::ptr::eq(source, destination),
+        )?;
+        self.copy_buffer_to_buffer(
+            source,
+            source_offset,
+            destination,
+            destination_offset,
+            copy_size,
+        );
+        Ok(())
+    }
+
     /// Copy data from a buffer to a texture.
     ///
     /// # Panics
@@ -2128,6 +3996,17 @@ impl CommandEncoder {
         let id = self.id.as_ref().unwrap();
         Context::command_encoder_pop_debug_group(&*self.context, id);
     }
+
+    /// Pushes a debug group that is popped when the returned guard is dropped.
+    ///
+    /// Useful to annotate copies and clears recorded outside of any pass in GPU captures.
+    pub fn debug_group(&mut self, label: &str) -> DebugGroupGuard<'_, Self> {
+        self.push_debug_group(label);
+        DebugGroupGuard {
+            pass: self,
+            pop: Self::pop_debug_group,
+        }
+    }
 }
 
 /// [`Features::TIMESTAMP_QUERY`] must be enabled on the device in order to call these functions.
@@ -2236,6 +4115,16 @@ impl<'a> RenderPass<'a> {
         )
     }
 
+    /// Assign vertex buffers to consecutive slots, starting at `start_slot`.
+    ///
+    /// This simply calls [`set_vertex_buffer`](RenderPass::set_vertex_buffer) for each
+    /// slice, with `buffer_slices[i]` going to slot `start_slot + i`.
+    pub fn set_vertex_buffers(&mut self, start_slot: u32, buffer_slices: &[BufferSlice<'a>]) {
+        for (slot, &buffer_slice) in (start_slot..).zip(buffer_slices) {
+            self.set_vertex_buffer(slot, buffer_slice);
+        }
+    }
+
     /// Sets the scissor region.
     ///
     /// Subsequent draw calls will discard any fragments that fall outside this region.
@@ -2243,6 +4132,28 @@ impl<'a> RenderPass<'a> {
         self.id.set_scissor_rect(x, y, width, height);
     }
 
+    /// Sets the scissor region, clamped to a render target of `target_width` by
+    /// `target_height`.
+    ///
+    /// A scissor rect reaching past the attachments is a validation error, which this avoids
+    /// for rects coming from layout code that can overflow. If nothing of the rect is left
+    /// after clamping, the call is skipped and the previous scissor region stays in effect.
+    pub fn set_scissor_rect_checked(
+        &mut self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        target_width: u32,
+        target_height: u32,
+    ) {
+        if let Some((x, y, width, height)) =
+            clamp_scissor_rect(x, y, width, height, target_width, target_height)
+        {
+            self.set_scissor_rect(x, y, width, height);
+        }
+    }
+
     /// Sets the viewport region.
     ///
     /// Subsequent draw calls will draw any fragments in this region.
@@ -2279,6 +4190,18 @@ impl<'a> RenderPass<'a> {
         self.id.pop_debug_group();
     }
 
+    /// Pushes a debug group that is popped when the returned guard is dropped.
+    ///
+    /// The pass stays accessible through the guard, so commands recorded through it
+    /// end up inside the group, and the group is closed even on early return.
+    pub fn debug_group(&mut self, label: &str) -> DebugGroupGuard<'_, Self> {
+        self.push_debug_group(label);
+        DebugGroupGuard {
+            pass: self,
+            pop: Self::pop_debug_group,
+        }
+    }
+
     /// Draws indexed primitives using the active index buffer and the active vertex buffers.
     ///
     /// The active index buffer can be set with [`RenderPass::set_index_buffer`], while the active
@@ -2360,7 +4283,8 @@ impl<'a> RenderPass<'a> {
     /// }
     /// ```
     ///
-    /// These draw structures are expected to be tightly packed.
+    /// These draw structures are expected to be tightly packed, [`util::DrawIndirectArgs`] being
+    /// laid out the same way with a stride of 16 bytes.
     pub fn multi_draw_indirect(
         &mut self,
         indirect_buffer: &'a Buffer,
@@ -2390,7 +4314,8 @@ impl<'a> RenderPass<'a> {
     /// }
     /// ```
     ///
-    /// These draw structures are expected to be tightly packed.
+    /// These draw structures are expected to be tightly packed, [`util::DrawIndexedIndirectArgs`]
+    /// being laid out the same way with a stride of 20 bytes.
     pub fn multi_draw_indexed_indirect(
         &mut self,
         indirect_buffer: &'a Buffer,
@@ -2424,7 +4349,8 @@ impl<'a> RenderPass<'a> {
     /// }
     /// ```
     ///
-    /// These draw structures are expected to be tightly packed.
+    /// These draw structures are expected to be tightly packed, [`util::DrawIndirectArgs`] being
+    /// laid out the same way with a stride of 16 bytes.
     ///
     /// The structure expected in `count_buffer` is the following:
     ///
@@ -2473,7 +4399,8 @@ impl<'a> RenderPass<'a> {
     /// }
     /// ```
     ///
-    /// These draw structures are expected to be tightly packed.
+    /// These draw structures are expected to be tightly packed, [`util::DrawIndexedIndirectArgs`]
+    /// being laid out the same way with a stride of 20 bytes.
     ///
     /// The structure expected in `count_buffer` is the following:
     ///
@@ -2612,6 +4539,18 @@ impl<'a> ComputePass<'a> {
         self.id.pop_debug_group();
     }
 
+    /// Pushes a debug group that is popped when the returned guard is dropped.
+    ///
+    /// The pass stays accessible through the guard, so commands recorded through it
+    /// end up inside the group, and the group is closed even on early return.
+    pub fn debug_group(&mut self, label: &str) -> DebugGroupGuard<'_, Self> {
+        self.push_debug_group(label);
+        DebugGroupGuard {
+            pass: self,
+            pop: Self::pop_debug_group,
+        }
+    }
+
     /// Dispatches compute work operations.
     ///
     /// `x`, `y` and `z` denote the number of work groups to dispatch in each dimension.
@@ -2619,6 +4558,25 @@ impl<'a> ComputePass<'a> {
         ComputePassInner::dispatch(&mut self.id, x, y, z);
     }
 
+    /// Dispatches enough work groups of `workgroup_size` to cover `total_x * total_y * total_z`
+    /// invocations.
+    ///
+    /// The number of work groups in each dimension is rounded up, so the shader has to ignore
+    /// invocations past the totals.
+    pub fn dispatch_workgroups_for(
+        &mut self,
+        total_x: u32,
+        total_y: u32,
+        total_z: u32,
+        workgroup_size: (u32, u32, u32),
+    ) {
+        self.dispatch(
+            workgroup_count(total_x, workgroup_size.0),
+            workgroup_count(total_y, workgroup_size.1),
+            workgroup_count(total_z, workgroup_size.2),
+        );
+    }
+
     /// Dispatches compute work operations, based on the contents of the `indirect_buffer`.
     pub fn dispatch_indirect(
         &mut self,
@@ -2689,6 +4647,8 @@ impl<'a> RenderBundleEncoder<'a> {
         RenderBundle {
             context: Arc::clone(&self.context),
             id: Context::render_bundle_encoder_finish(&*self.context, self.id, desc),
+            label: desc.label.map(str::to_owned),
+            draw_count: self.draw_count,
         }
     }
 
@@ -2750,6 +4710,7 @@ impl<'a> RenderBundleEncoder<'a> {
     ///
     /// The active vertex buffers can be set with [`RenderBundleEncoder::set_vertex_buffer`].
     pub fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
+        self.draw_count += 1;
         RenderInner::draw(&mut self.id, vertices, instances)
     }
 
@@ -2758,6 +4719,7 @@ impl<'a> RenderBundleEncoder<'a> {
     /// The active index buffer can be set with [`RenderBundleEncoder::set_index_buffer`], while the active
     /// vertex buffers can be set with [`RenderBundleEncoder::set_vertex_buffer`].
     pub fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>) {
+        self.draw_count += 1;
         RenderInner::draw_indexed(&mut self.id, indices, base_vertex, instances);
     }
 
@@ -2777,6 +4739,7 @@ impl<'a> RenderBundleEncoder<'a> {
     /// }
     /// ```
     pub fn draw_indirect(&mut self, indirect_buffer: &'a Buffer, indirect_offset: BufferAddress) {
+        self.draw_count += 1;
         self.id.draw_indirect(&indirect_buffer.id, indirect_offset);
     }
 
@@ -2803,6 +4766,7 @@ impl<'a> RenderBundleEncoder<'a> {
         indirect_buffer: &'a Buffer,
         indirect_offset: BufferAddress,
     ) {
+        self.draw_count += 1;
         self.id
             .draw_indexed_indirect(&indirect_buffer.id, indirect_offset);
     }
@@ -2853,6 +4817,48 @@ impl Queue {
         Context::queue_write_buffer(&*self.context, &self.id, &buffer.id, offset, data)
     }
 
+    /// Schedule a write of `size` bytes into `buffer` starting at `offset`, returning a
+    /// view to fill with the data.
+    ///
+    /// The view maps a staging buffer created for the write, so the data is written in place
+    /// rather than gathered into a slice beforehand. When the view is dropped, a copy out of
+    /// the staging buffer is enqueued to happen at the start of the next `submit()` call,
+    /// after any writes made with [`Queue::write_buffer`] before it. Nothing is written if the
+    /// view is dropped while panicking.
+    ///
+    /// Returns `None` if `buffer` lacks the [`COPY_DST`](BufferUsage::COPY_DST) usage, or if
+    /// `offset` or `size` is not a multiple of [`COPY_BUFFER_ALIGNMENT`] or the range doesn't
+    /// fit in the buffer.
+    pub fn write_buffer_with<'a>(
+        &'a self,
+        buffer: &'a Buffer,
+        offset: BufferAddress,
+        size: BufferSize,
+    ) -> Option<QueueWriteBufferView<'a>> {
+        let size = size.get();
+        if !buffer.usage.contains(BufferUsage::COPY_DST)
+            || offset % COPY_BUFFER_ALIGNMENT != 0
+            || size % COPY_BUFFER_ALIGNMENT != 0
+            || offset.checked_add(size)? > buffer.size
+        {
+            return None;
+        }
+        let staging = self.device.create_buffer(&BufferDescriptor {
+            label: Some("write_buffer_with staging"),
+            size,
+            usage: BufferUsage::MAP_WRITE | BufferUsage::COPY_SRC,
+            mapped_at_creation: true,
+        });
+        let data = Context::buffer_get_mapped_range(&*staging.context, &staging.id, 0..size);
+        Some(QueueWriteBufferView {
+            queue: self,
+            buffer,
+            offset,
+            staging,
+            data: Some(data),
+        })
+    }
+
     /// Schedule a data write into `texture`.
     ///
     /// This method is intended to have low performance costs.
@@ -2868,15 +4874,91 @@ impl Queue {
         Context::queue_write_texture(&*self.context, &self.id, texture, data, data_layout, size)
     }
 
+    /// Schedule a write of tightly packed texel data into `texture`.
+    ///
+    /// Rows follow each other without padding, as in most image files. Unlike copies from
+    /// buffers, writes from the queue don't need rows aligned to
+    /// [`COPY_BYTES_PER_ROW_ALIGNMENT`], so the data is uploaded as is. The texels are in
+    /// the [`format`](Texture::format) of the texture.
+    ///
+    /// # Panics
+    ///
+    /// - `data` is shorter than `size` texels of the texture format.
+    pub fn write_texture_packed(&self, texture: ImageCopyTexture, data: &[u8], size: Extent3d) {
+        let format = texture.texture.format();
+        let (layout, len) = packed_data_layout(format, size);
+        assert!(
+            data.len() >= len,
+            "Packed texture data is {} bytes, but {} are needed for {:?} of {:?}",
+            data.len(),
+            len,
+            size,
+            format
+        );
+        self.write_texture(texture, &data[..len], layout, size);
+    }
+
     /// Submits a series of finished command buffers for execution.
-    pub fn submit<I: IntoIterator<Item = CommandBuffer>>(&self, command_buffers: I) {
-        Context::queue_submit(
-            &*self.context,
+    ///
+    /// Returns the index of the submission, to wait for with
+    /// [`Maintain::WaitForSubmissionIndex`].
+    pub fn submit<I: IntoIterator<Item = CommandBuffer>>(
+        &self,
+        command_buffers: I,
+    ) -> SubmissionIndex {
+        let staged_writes = std::mem::take(&mut *self.device.staged_writes.lock());
+        self.device.submit(
             &self.id,
-            command_buffers
+            staged_writes
                 .into_iter()
+                .chain(command_buffers)
                 .map(|mut comb| comb.id.take().unwrap()),
-        );
+        )
+    }
+
+    /// Submits a series of finished command buffers for execution, and blocks until
+    /// the GPU is done executing them.
+    ///
+    /// This is handy for readbacks and deterministic tests. `device` is polled with
+    /// [`Maintain::WaitForSubmissionIndex`] for this submission, so work submitted after it
+    /// from other threads isn't waited for.
+    ///
+    /// On the web, this does not block, same as [`Device::poll`].
+    pub fn submit_and_wait<I: IntoIterator<Item = CommandBuffer>>(
+        &self,
+        device: &Device,
+        command_buffers: I,
+    ) {
+        let index = self.submit(command_buffers);
+        device.poll(Maintain::WaitForSubmissionIndex(index));
+    }
+
+    /// Calls `callback` once all the work submitted so far to this queue is done.
+    ///
+    /// The callback runs on the thread calling [`Device::poll`] or [`Queue::submit`] once the
+    /// work is seen to be done, so one of them has to be called for it to ever run. If no
+    /// work is pending, it runs on the next of these calls.
+    ///
+    /// The callback is dropped without being called if the device is lost or dropped first.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn on_submitted_work_done(&self, callback: impl FnOnce() + Send + 'static) {
+        let mut submissions = self.device.submissions.lock();
+        let index = submissions.last_index;
+        submissions.on_done(index, Box::new(callback));
+    }
+
+    /// Calls `callback` once the submission `index` is done, as with
+    /// [`Queue::on_submitted_work_done`].
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn on_submission_done(
+        &self,
+        index: SubmissionIndex,
+        callback: impl FnOnce() + Send + 'static,
+    ) {
+        self.device
+            .submissions
+            .lock()
+            .on_done(index.0, Box::new(callback));
     }
 
     /// Gets the amount of nanoseconds each tick of a timestamp query represents.
@@ -2929,12 +5011,42 @@ impl SwapChain {
             SwapChainStatus::Lost => Err(SwapChainError::Lost),
         }
     }
+
+    /// Returns the next texture to be presented by the swapchain for drawing, recreating
+    /// the swapchain if needed.
+    ///
+    /// If acquiring the frame fails with [`SwapChainError::Outdated`] or [`SwapChainError::Lost`],
+    /// the swapchain is recreated once for `surface` with `desc`, and acquiring is retried exactly
+    /// one more time. Other errors are returned unchanged.
+    pub fn get_current_frame_retry(
+        &mut self,
+        device: &Device,
+        surface: &Surface,
+        desc: &SwapChainDescriptor,
+    ) -> Result<SwapChainFrame, SwapChainError> {
+        match self.get_current_frame() {
+            Err(SwapChainError::Outdated) | Err(SwapChainError::Lost) => {
+                *self = device.create_swap_chain(surface, desc);
+                self.get_current_frame()
+            }
+            result => result,
+        }
+    }
 }
 
 /// Type for the callback of uncaptured error handler
 pub trait UncapturedErrorHandler: Fn(Error) + Send + 'static {}
 impl<T> UncapturedErrorHandler for T where T: Fn(Error) + Send + 'static {}
 
+/// Filter for error scopes, selecting which kind of errors a scope captures.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum ErrorFilter {
+    /// Catch only out-of-memory errors.
+    OutOfMemory,
+    /// Catch only validation errors.
+    Validation,
+}
+
 /// Error type
 #[derive(Debug)]
 pub enum Error {
@@ -10,13 +10,23 @@
 /// This example specifies a list of three [VertexAttribute](crate::VertexAttribute),
 /// each with the given `shader_location` and `format`.
 /// Offsets are calculated automatically.
+///
+/// An attribute may be prefixed with `@offset` to place it at an explicit offset,
+/// e.g. to account for padding in a `#[repr(C)]` struct. Subsequent attributes
+/// continue from there:
+/// ```
+/// # use wgpu::vertex_attr_array;
+/// let attrs = vertex_attr_array![0 => Float32x3, @16 1 => Float32x4, 2 => Float32x2];
+/// assert_eq!(attrs[1].offset, 16);
+/// assert_eq!(attrs[2].offset, 32);
+/// ```
 #[macro_export]
 macro_rules! vertex_attr_array {
-    ($($loc:expr => $fmt:ident),* $(,)?) => {
-        $crate::vertex_attr_array!([] ; 0; $($loc => $fmt ,)*)
-    };
     ([$($t:expr,)*] ; $off:expr ;) => { [$($t,)*] };
-    ([$($t:expr,)*] ; $off:expr ; $loc:expr => $item:ident, $($ll:expr => $ii:ident ,)*) => {
+    ([$($t:expr,)*] ; $off:expr ; @ $explicit:tt $loc:expr => $item:ident $(, $($rest:tt)*)?) => {
+        $crate::vertex_attr_array!([$($t,)*] ; $explicit ; $loc => $item $(, $($rest)*)?)
+    };
+    ([$($t:expr,)*] ; $off:expr ; $loc:expr => $item:ident $(, $($rest:tt)*)?) => {
         $crate::vertex_attr_array!(
             [$($t,)*
             $crate::VertexAttribute {
@@ -25,9 +35,12 @@ macro_rules! vertex_attr_array {
                 shader_location: $loc,
             },];
             $off + $crate::VertexFormat :: $item.size();
-            $($ll => $ii ,)*
+            $($($rest)*)?
         )
     };
+    ($($t:tt)*) => {
+        $crate::vertex_attr_array!([] ; 0; $($t)*)
+    };
 }
 
 #[test]
@@ -41,11 +54,401 @@ fn test_vertex_attr_array() {
     assert_eq!(attrs[1].shader_location, 3);
 }
 
+#[test]
+fn test_vertex_attr_array_explicit_offsets() {
+    use crate::{VertexAttribute, VertexFormat};
+
+    let attrs = vertex_attr_array![
+        0 => Float32x3,
+        @16 1 => Float32x4,
+        2 => Float32x2,
+        @48 3 => Uint32,
+    ];
+    let expected = [
+        VertexAttribute {
+            format: VertexFormat::Float32x3,
+            offset: 0,
+            shader_location: 0,
+        },
+        VertexAttribute {
+            format: VertexFormat::Float32x4,
+            offset: 16,
+            shader_location: 1,
+        },
+        VertexAttribute {
+            format: VertexFormat::Float32x2,
+            offset: 32,
+            shader_location: 2,
+        },
+        VertexAttribute {
+            format: VertexFormat::Uint32,
+            offset: 48,
+            shader_location: 3,
+        },
+    ];
+    assert_eq!(attrs.len(), expected.len());
+    for (attr, expected) in attrs.iter().zip(expected.iter()) {
+        assert_eq!(attr.format, expected.format);
+        assert_eq!(attr.offset, expected.offset);
+        assert_eq!(attr.shader_location, expected.shader_location);
+    }
+}
+
+/// Macro to produce an array of [BindGroupLayoutEntry](crate::BindGroupLayoutEntry).
+///
+/// Output has type: `[BindGroupLayoutEntry; _]`. Usage is as follows:
+/// ```
+/// # use wgpu::{bind_group_layout_entries, TextureSampleType, TextureViewDimension};
+/// let entries = bind_group_layout_entries![
+///     0 => (FRAGMENT, Texture {
+///         multisampled: false,
+///         sample_type: TextureSampleType::Float { filterable: true },
+///         view_dimension: TextureViewDimension::D2,
+///     }),
+///     1 => (VERTEX | FRAGMENT, Sampler { filtering: true, comparison: false }),
+/// ];
+/// ```
+/// Each entry is given as `binding => (visibility, binding type)`, where the visibility is a list
+/// of [ShaderStage](crate::ShaderStage) flags and the binding type is a variant of
+/// [BindingType](crate::BindingType).
+/// The `count` is `None`, unless overridden with a trailing `count = N` for binding arrays:
+/// ```
+/// # use wgpu::{bind_group_layout_entries, TextureSampleType, TextureViewDimension};
+/// let entries = bind_group_layout_entries![
+///     0 => (FRAGMENT, Texture {
+///         multisampled: false,
+///         sample_type: TextureSampleType::Float { filterable: true },
+///         view_dimension: TextureViewDimension::D2,
+///     }, count = 4),
+/// ];
+/// ```
+#[macro_export]
+macro_rules! bind_group_layout_entries {
+    (@count) => { None };
+    (@count $count:expr) => {
+        Some(std::num::NonZeroU32::new($count).expect("binding array count must be non-zero"))
+    };
+    ($($binding:expr => ($($vis:ident)|+, $ty:ident $body:tt $(, count = $count:expr)?)),* $(,)?) => {
+        [$(
+            $crate::BindGroupLayoutEntry {
+                binding: $binding,
+                visibility: $($crate::ShaderStage::$vis)|+,
+                ty: $crate::BindingType::$ty $body,
+                count: $crate::bind_group_layout_entries!(@count $($count)?),
+            },
+        )*]
+    };
+}
+
+#[test]
+fn test_bind_group_layout_entries() {
+    use crate::{
+        BindGroupLayoutEntry, BindingType, BufferBindingType, ShaderStage, TextureSampleType,
+        TextureViewDimension,
+    };
+
+    let entries = bind_group_layout_entries![
+        0 => (VERTEX, Buffer {
+            ty: BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        }),
+        2 => (FRAGMENT, Texture {
+            multisampled: false,
+            sample_type: TextureSampleType::Float { filterable: true },
+            view_dimension: TextureViewDimension::D2,
+        }, count = 4),
+        3 => (VERTEX | FRAGMENT, Sampler { filtering: true, comparison: false }),
+    ];
+    let expected = [
+        BindGroupLayoutEntry {
+            binding: 0,
+            visibility: ShaderStage::VERTEX,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        },
+        BindGroupLayoutEntry {
+            binding: 2,
+            visibility: ShaderStage::FRAGMENT,
+            ty: BindingType::Texture {
+                multisampled: false,
+                sample_type: TextureSampleType::Float { filterable: true },
+                view_dimension: TextureViewDimension::D2,
+            },
+            count: std::num::NonZeroU32::new(4),
+        },
+        BindGroupLayoutEntry {
+            binding: 3,
+            visibility: ShaderStage::VERTEX | ShaderStage::FRAGMENT,
+            ty: BindingType::Sampler {
+                filtering: true,
+                comparison: false,
+            },
+            count: None,
+        },
+    ];
+    assert_eq!(entries, expected);
+}
+
+/// Macro to produce an array of [BindGroupEntry](crate::BindGroupEntry).
+///
+/// Output has type: `[BindGroupEntry; _]`. Usage is as follows:
+/// ```no_run
+/// # use wgpu::bind_group_entries;
+/// # fn example(buffer: &wgpu::Buffer, view: &wgpu::TextureView) {
+/// let entries = bind_group_entries![
+///     0 => buffer.as_entire_binding(),
+///     1 => wgpu::BindingResource::TextureView(view),
+/// ];
+/// # }
+/// ```
+/// Each entry is given as `binding => resource`, where the resource is any expression that
+/// converts into a [BindingResource](crate::BindingResource).
+///
+/// This pairs with [bind_group_layout_entries](crate::bind_group_layout_entries), so the binding
+/// numbers of a layout and its bind groups can be kept visually aligned.
+#[macro_export]
+macro_rules! bind_group_entries {
+    ($($binding:expr => $resource:expr),* $(,)?) => {
+        [$(
+            $crate::BindGroupEntry {
+                binding: $binding,
+                resource: ::std::convert::Into::into($resource),
+            },
+        )*]
+    };
+}
+
+#[test]
+#[cfg(not(target_arch = "wasm32"))]
+fn test_bind_group_entries() {
+    use crate::{BindingResource, BufferBinding};
+
+    let (device, _queue) = match crate::util::test_device() {
+        Some(device) => device,
+        None => return,
+    };
+    let buffer = device.create_buffer(&crate::BufferDescriptor {
+        label: None,
+        size: 64,
+        usage: crate::BufferUsage::UNIFORM,
+        mapped_at_creation: false,
+    });
+    let sampler = device.create_sampler(&crate::SamplerDescriptor::default());
+
+    let entries = bind_group_entries![
+        0 => buffer.as_entire_binding(),
+        3 => BindingResource::Sampler(&sampler),
+    ];
+    assert_eq!(entries.len(), 2);
+    assert_eq!(entries[0].binding, 0);
+    match entries[0].resource {
+        BindingResource::Buffer(BufferBinding {
+            buffer: bound,
+            offset: 0,
+            size: None,
+        }) => assert!(std::ptr::eq(bound, &buffer)),
+        ref other => panic!("Unexpected resource {:?}", other),
+    }
+    assert_eq!(entries[1].binding, 3);
+    assert!(matches!(
+        entries[1].resource,
+        BindingResource::Sampler(bound) if std::ptr::eq(bound, &sampler)
+    ));
+}
+
+/// Macro to produce an array of [PushConstantRange](crate::PushConstantRange), for
+/// [PipelineLayoutDescriptor::push_constant_ranges](crate::PipelineLayoutDescriptor::push_constant_ranges).
+///
+/// Output has type: `[PushConstantRange; _]`. Usage is as follows:
+/// ```
+/// # use wgpu::push_constant_ranges;
+/// let ranges = push_constant_ranges![
+///     VERTEX => 0..64,
+///     VERTEX | FRAGMENT => 64..80,
+/// ];
+/// ```
+/// Each range is given as `stages => range`, where the stages are a list of
+/// [ShaderStage](crate::ShaderStage) flags and the range is in bytes, aligned to
+/// [PUSH_CONSTANT_ALIGNMENT](crate::PUSH_CONSTANT_ALIGNMENT).
+///
+/// Push constants need [Features::PUSH_CONSTANTS](crate::Features::PUSH_CONSTANTS).
+#[macro_export]
+macro_rules! push_constant_ranges {
+    ($($($stage:ident)|+ => $range:expr),* $(,)?) => {
+        [$(
+            $crate::PushConstantRange {
+                stages: $($crate::ShaderStage::$stage)|+,
+                range: $range,
+            },
+        )*]
+    };
+}
+
+#[test]
+fn test_push_constant_ranges() {
+    use crate::{PushConstantRange, ShaderStage};
+
+    let ranges = push_constant_ranges![
+        VERTEX => 0..64,
+        VERTEX | FRAGMENT => 64..80,
+    ];
+    assert_eq!(
+        ranges,
+        [
+            PushConstantRange {
+                stages: ShaderStage::VERTEX,
+                range: 0..64,
+            },
+            PushConstantRange {
+                stages: ShaderStage::VERTEX | ShaderStage::FRAGMENT,
+                range: 64..80,
+            },
+        ]
+    );
+}
+
+/// Macro to produce an array of [ColorTargetState](crate::ColorTargetState), for
+/// [FragmentState::targets](crate::FragmentState::targets).
+///
+/// Output has type: `[ColorTargetState; _]`. Usage is as follows:
+/// ```
+/// # use wgpu::color_targets;
+/// # let swap_chain_format = wgpu::TextureFormat::Bgra8UnormSrgb;
+/// let targets = color_targets![
+///     Rgba8Unorm => opaque,
+///     Rgba16Float => additive,
+///     (swap_chain_format) => alpha_blend,
+/// ];
+/// ```
+/// Each target is given as `format => preset`, where the format is either a
+/// [TextureFormat](crate::TextureFormat) variant or a parenthesized expression,
+/// and the preset one of:
+///
+/// - `opaque`: no blending.
+/// - `alpha_blend`: standard alpha blending of non-premultiplied colors.
+/// - `premultiplied`: alpha blending of premultiplied colors.
+/// - `additive`: the color is added to the target.
+/// - `multiply`: the target is multiplied by the color.
+///
+/// All targets write every channel.
+#[macro_export]
+macro_rules! color_targets {
+    (@format $format:ident) => { $crate::TextureFormat::$format };
+    (@format ($format:expr)) => { $format };
+    (@blend opaque) => { None };
+    (@blend alpha_blend) => { Some($crate::BlendState::ALPHA_BLENDING) };
+    (@blend premultiplied) => { Some($crate::BlendState::PREMULTIPLIED_ALPHA_BLENDING) };
+    (@blend additive) => {
+        Some(<$crate::BlendState as $crate::util::BlendStateExt>::ADDITIVE)
+    };
+    (@blend multiply) => {
+        Some(<$crate::BlendState as $crate::util::BlendStateExt>::MULTIPLY)
+    };
+    ($($format:tt => $preset:ident),* $(,)?) => {
+        [$(
+            $crate::ColorTargetState {
+                format: $crate::color_targets!(@format $format),
+                blend: $crate::color_targets!(@blend $preset),
+                write_mask: $crate::ColorWrite::ALL,
+            },
+        )*]
+    };
+}
+
+#[test]
+fn test_color_targets() {
+    use crate::{
+        BlendComponent, BlendFactor, BlendOperation, BlendState, ColorWrite, TextureFormat,
+    };
+
+    let format = TextureFormat::Bgra8UnormSrgb;
+    let targets = color_targets![
+        Rgba8Unorm => opaque,
+        Rgba16Float => alpha_blend,
+        (format) => premultiplied,
+        R32Float => additive,
+    ];
+    let component = |src_factor, dst_factor| BlendComponent {
+        src_factor,
+        dst_factor,
+        operation: BlendOperation::Add,
+    };
+
+    assert_eq!(targets.len(), 4);
+    assert_eq!(targets[0].format, TextureFormat::Rgba8Unorm);
+    assert_eq!(targets[0].blend, None);
+    assert_eq!(targets[1].format, TextureFormat::Rgba16Float);
+    assert_eq!(
+        targets[1].blend,
+        Some(BlendState {
+            color: component(BlendFactor::SrcAlpha, BlendFactor::OneMinusSrcAlpha),
+            alpha: component(BlendFactor::One, BlendFactor::OneMinusSrcAlpha),
+        })
+    );
+    assert_eq!(targets[2].format, TextureFormat::Bgra8UnormSrgb);
+    assert_eq!(
+        targets[2].blend,
+        Some(BlendState {
+            color: component(BlendFactor::One, BlendFactor::OneMinusSrcAlpha),
+            alpha: component(BlendFactor::One, BlendFactor::OneMinusSrcAlpha),
+        })
+    );
+    assert_eq!(
+        targets[3].blend,
+        Some(BlendState {
+            color: component(BlendFactor::One, BlendFactor::One),
+            alpha: component(BlendFactor::One, BlendFactor::One),
+        })
+    );
+    for target in targets.iter() {
+        assert_eq!(target.write_mask, ColorWrite::ALL);
+    }
+}
+
 /// Macro to load a SPIR-V module statically.
 ///
 /// It ensures the word alignment as well as the magic number.
+///
+/// The module is labeled with its path, unless a label is given:
+/// ```ignore
+/// let desc = wgpu::include_spirv!("shader.vert.spv", label = "lighting");
+/// ```
+///
+/// It is created with [ShaderFlags::VALIDATION](crate::ShaderFlags::VALIDATION), unless
+/// flags are given after the path or label. To pick them once for the whole program, e.g.
+/// to only validate in debug builds:
+/// ```ignore
+/// const SHADER_FLAGS: wgpu::ShaderFlags = if cfg!(debug_assertions) {
+///     wgpu::ShaderFlags::VALIDATION
+/// } else {
+///     wgpu::ShaderFlags::empty()
+/// };
+///
+/// let desc = wgpu::include_spirv!("shader.vert.spv", flags = SHADER_FLAGS);
+/// ```
 #[macro_export]
 macro_rules! include_spirv {
+    ($path:literal, label = $label:expr, flags = $flags:expr $(,)?) => {
+        {
+            //log::info!("including '{}'", $path);
+            $crate::ShaderModuleDescriptor {
+                label: Some($label),
+                source: $crate::util::make_spirv(include_bytes!($path)),
+                flags: $flags,
+            }
+        }
+    };
+    ($path:literal, label = $label:expr $(,)?) => {
+        $crate::include_spirv!($path, label = $label, flags = $crate::ShaderFlags::VALIDATION)
+    };
+    ($path:literal, flags = $flags:expr $(,)?) => {
+        $crate::include_spirv!($path, label = $path, flags = $flags)
+    };
     ($($token:tt)*) => {
         {
             //log::info!("including '{}'", $($token)*);
@@ -58,9 +461,75 @@ macro_rules! include_spirv {
     };
 }
 
+/// Macro to load a SPIR-V module statically, skipping shader validation.
+///
+/// Word alignment and the magic number are still checked, but the module is
+/// created with empty [ShaderFlags](crate::ShaderFlags), so naga does not validate it.
+/// The caller takes responsibility for the correctness of the module, e.g. when it
+/// has already been validated by an external toolchain.
+///
+/// Like [include_spirv](crate::include_spirv), it accepts a `label = "..."` argument.
+#[macro_export]
+macro_rules! include_spirv_raw {
+    ($path:literal, label = $label:expr $(,)?) => {
+        {
+            //log::info!("including '{}'", $path);
+            $crate::ShaderModuleDescriptor {
+                label: Some($label),
+                source: $crate::util::make_spirv(include_bytes!($path)),
+                flags: $crate::ShaderFlags::empty(),
+            }
+        }
+    };
+    ($($token:tt)*) => {
+        {
+            //log::info!("including '{}'", $($token)*);
+            $crate::ShaderModuleDescriptor {
+                label: Some($($token)*),
+                source: $crate::util::make_spirv(include_bytes!($($token)*)),
+                flags: $crate::ShaderFlags::empty(),
+            }
+        }
+    };
+}
+
+#[test]
+fn test_include_spirv_raw() {
+    let desc = include_spirv_raw!("../examples/texture-arrays/shader.vert.spv");
+    assert!(desc.flags.is_empty());
+    assert!(matches!(desc.source, crate::ShaderSource::SpirV(_)));
+}
+
 /// Macro to load a WGSL module statically.
+///
+/// The module is labeled with its path, unless a label is given:
+/// ```ignore
+/// let desc = wgpu::include_wgsl!("shader.wgsl", label = "lighting");
+/// ```
+///
+/// It is created with all the [ShaderFlags](crate::ShaderFlags), unless flags are given
+/// after the path or label, the same way as with [include_spirv](crate::include_spirv):
+/// ```ignore
+/// let desc = wgpu::include_wgsl!("shader.wgsl", label = "lighting", flags = SHADER_FLAGS);
+/// ```
 #[macro_export]
 macro_rules! include_wgsl {
+    ($path:literal, label = $label:expr, flags = $flags:expr $(,)?) => {
+        {
+            //log::info!("including '{}'", $path);
+            $crate::ShaderModuleDescriptor {
+                label: Some($label),
+                source: $crate::ShaderSource::Wgsl(include_str!($path).into()),
+                flags: $flags,
+            }
+        }
+    };
+    ($path:literal, label = $label:expr $(,)?) => {
+        $crate::include_wgsl!($path, label = $label, flags = $crate::ShaderFlags::all())
+    };
+    ($path:literal, flags = $flags:expr $(,)?) => {
+        $crate::include_wgsl!($path, label = $path, flags = $flags)
+    };
     ($($token:tt)*) => {
         {
             //log::info!("including '{}'", $($token)*);
@@ -72,3 +541,89 @@ macro_rules! include_wgsl {
         }
     };
 }
+
+#[test]
+fn test_include_wgsl_label() {
+    let desc = include_wgsl!("../examples/hello-triangle/shader.wgsl");
+    assert_eq!(desc.label, Some("../examples/hello-triangle/shader.wgsl"));
+    let desc = include_wgsl!("../examples/hello-triangle/shader.wgsl", label = "triangle");
+    assert_eq!(desc.label, Some("triangle"));
+    assert!(matches!(desc.source, crate::ShaderSource::Wgsl(_)));
+}
+
+#[test]
+fn test_include_spirv_label() {
+    let desc = include_spirv!("../examples/texture-arrays/shader.vert.spv");
+    assert_eq!(
+        desc.label,
+        Some("../examples/texture-arrays/shader.vert.spv")
+    );
+    let desc = include_spirv!(
+        "../examples/texture-arrays/shader.vert.spv",
+        label = "vertex"
+    );
+    assert_eq!(desc.label, Some("vertex"));
+    assert_eq!(desc.flags, crate::ShaderFlags::VALIDATION);
+    let desc = include_spirv_raw!(
+        "../examples/texture-arrays/shader.vert.spv",
+        label = "vertex"
+    );
+    assert_eq!(desc.label, Some("vertex"));
+    assert!(desc.flags.is_empty());
+}
+
+#[test]
+fn test_include_shader_flags() {
+    use crate::ShaderFlags;
+
+    let desc = include_wgsl!("../examples/hello-triangle/shader.wgsl");
+    assert_eq!(desc.flags, ShaderFlags::all());
+    let desc = include_wgsl!(
+        "../examples/hello-triangle/shader.wgsl",
+        flags = ShaderFlags::VALIDATION
+    );
+    assert_eq!(desc.label, Some("../examples/hello-triangle/shader.wgsl"));
+    assert_eq!(desc.flags, ShaderFlags::VALIDATION);
+
+    let desc = include_spirv!("../examples/texture-arrays/shader.vert.spv");
+    assert_eq!(desc.flags, ShaderFlags::VALIDATION);
+    let desc = include_spirv!(
+        "../examples/texture-arrays/shader.vert.spv",
+        label = "vertex",
+        flags = ShaderFlags::empty(),
+    );
+    assert_eq!(desc.label, Some("vertex"));
+    assert!(desc.flags.is_empty());
+}
+
+/// Macro to load a GLSL module statically, compiling it to SPIR-V through naga.
+///
+/// The shader stage has to be given as one of `Vertex`, `Fragment` or `Compute`:
+/// ```ignore
+/// let desc = wgpu::include_glsl!("shader.frag", stage = Fragment);
+/// ```
+#[cfg(feature = "glsl")]
+#[macro_export]
+macro_rules! include_glsl {
+    (@stage Vertex) => { $crate::ShaderStage::VERTEX };
+    (@stage Fragment) => { $crate::ShaderStage::FRAGMENT };
+    (@stage Compute) => { $crate::ShaderStage::COMPUTE };
+    ($path:literal, stage = $stage:ident $(,)?) => {
+        {
+            //log::info!("including '{}'", $path);
+            $crate::ShaderModuleDescriptor {
+                label: Some($path),
+                source: $crate::util::make_glsl(include_str!($path), $crate::include_glsl!(@stage $stage)),
+                flags: $crate::ShaderFlags::VALIDATION,
+            }
+        }
+    };
+}
+
+#[cfg(feature = "glsl")]
+#[test]
+fn test_include_glsl() {
+    let desc = include_glsl!("../examples/texture-arrays/shader.vert", stage = Vertex);
+    assert_eq!(desc.label, Some("../examples/texture-arrays/shader.vert"));
+    assert!(matches!(desc.source, crate::ShaderSource::SpirV(_)));
+}
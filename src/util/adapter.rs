@@ -0,0 +1,142 @@
+use std::{future::Future, path::Path, pin::Pin};
+
+use crate::{Adapter, Device, DeviceDescriptor, Features, Limits, Queue, RequestDeviceError};
+
+use super::{LimitsExt, MissingFeatures};
+
+/// Utility methods not meant to be in the main API.
+pub trait AdapterExt {
+    /// Requests a device with all the limits of the adapter, and the subset of `features`
+    /// it supports.
+    ///
+    /// Requested features the adapter lacks are logged and dropped rather than failing the
+    /// request, so check [`Device::features`] before relying on optional ones.
+    fn request_device_best(
+        &self,
+        features: Features,
+        trace_path: Option<&Path>,
+    ) -> Pin<Box<dyn Future<Output = Result<(Device, Queue), RequestDeviceError>> + Send>>;
+}
+
+impl AdapterExt for Adapter {
+    fn request_device_best(
+        &self,
+        features: Features,
+        trace_path: Option<&Path>,
+    ) -> Pin<Box<dyn Future<Output = Result<(Device, Queue), RequestDeviceError>> + Send>> {
+        let supported = self.features();
+        let missing = features - supported;
+        if !missing.is_empty() {
+            log::warn!(
+                "Adapter doesn't support the requested features {:?}, dropping them",
+                missing
+            );
+        }
+        Box::pin(self.request_device(
+            &DeviceDescriptor {
+                label: None,
+                features: features & supported,
+                limits: self.limits(),
+            },
+            trace_path,
+        ))
+    }
+}
+
+/// Describes a device with the `required` features, the subset of the `optional` ones the
+/// adapter supports, and `limits` lowered to the adapter's limits where they exceed them.
+///
+/// Returns an error if the adapter lacks any of the `required` features.
+pub fn negotiate_device_descriptor(
+    adapter: &Adapter,
+    required: Features,
+    optional: Features,
+    limits: Limits,
+) -> Result<DeviceDescriptor<'static>, MissingFeatures> {
+    negotiate(
+        adapter.features(),
+        &adapter.limits(),
+        required,
+        optional,
+        limits,
+    )
+}
+
+fn negotiate(
+    supported_features: Features,
+    supported_limits: &Limits,
+    required: Features,
+    optional: Features,
+    limits: Limits,
+) -> Result<DeviceDescriptor<'static>, MissingFeatures> {
+    let missing = required - supported_features;
+    if !missing.is_empty() {
+        return Err(MissingFeatures(missing));
+    }
+    Ok(DeviceDescriptor {
+        label: None,
+        features: required | (optional & supported_features),
+        limits: limits.clamp_to(supported_limits),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::negotiate;
+    use crate::{util::MissingFeatures, Features, Limits};
+
+    #[test]
+    fn negotiate_missing_required() {
+        let err = negotiate(
+            Features::TEXTURE_COMPRESSION_BC,
+            &Limits::default(),
+            Features::TEXTURE_COMPRESSION_BC | Features::TIMESTAMP_QUERY,
+            Features::empty(),
+            Limits::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err, MissingFeatures(Features::TIMESTAMP_QUERY));
+        assert_eq!(
+            err.to_string(),
+            "Adapter is missing the required features TIMESTAMP_QUERY"
+        );
+    }
+
+    #[test]
+    fn negotiate_optional_subset() {
+        let desc = negotiate(
+            Features::TEXTURE_COMPRESSION_BC | Features::PUSH_CONSTANTS,
+            &Limits::default(),
+            Features::TEXTURE_COMPRESSION_BC,
+            Features::PUSH_CONSTANTS | Features::TIMESTAMP_QUERY,
+            Limits::default(),
+        )
+        .unwrap();
+        assert_eq!(desc.label, None);
+        assert_eq!(
+            desc.features,
+            Features::TEXTURE_COMPRESSION_BC | Features::PUSH_CONSTANTS
+        );
+    }
+
+    #[test]
+    fn negotiate_clamps_limits() {
+        let supported = Limits {
+            max_push_constant_size: 128,
+            max_bind_groups: 2,
+            ..Limits::default()
+        };
+        let desc = negotiate(
+            Features::PUSH_CONSTANTS,
+            &supported,
+            Features::empty(),
+            Features::empty(),
+            Limits {
+                max_push_constant_size: 256,
+                ..Limits::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(desc.limits, supported);
+    }
+}
@@ -0,0 +1,225 @@
+use std::{error, fmt::Display};
+
+use crate::{
+    BindingResource, Buffer, BufferAddress, BufferBinding, BufferDescriptor, BufferSize,
+    BufferSlice, BufferUsage, Device, DynamicOffset, Label, Queue, BIND_BUFFER_ALIGNMENT,
+};
+
+/// Error returned by [`BufferArena::allocate`] when the requested size doesn't fit.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BufferArenaFull {
+    /// Requested size, in bytes.
+    pub size: BufferAddress,
+    /// Bytes left in the arena.
+    pub available: BufferAddress,
+}
+
+impl Display for BufferArenaFull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Buffer arena can't fit {} bytes, only {} are left",
+            self.size, self.available
+        )
+    }
+}
+
+impl error::Error for BufferArenaFull {}
+
+/// Range of a [`BufferArena`], returned by [`BufferArena::allocate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ArenaAllocation {
+    /// Offset of the range in the arena buffer, aligned to
+    /// [`BIND_BUFFER_ALIGNMENT`](crate::BIND_BUFFER_ALIGNMENT).
+    pub offset: BufferAddress,
+    /// Size of the range, in bytes.
+    pub size: BufferSize,
+}
+
+impl ArenaAllocation {
+    /// The offset of the range, to pass to `set_bind_group` with a
+    /// [`dynamic_binding`](BufferArena::dynamic_binding).
+    pub fn dynamic_offset(&self) -> DynamicOffset {
+        self.offset as DynamicOffset
+    }
+}
+
+/// Bump allocated ranges of a single buffer.
+#[derive(Debug)]
+struct BumpAllocator {
+    capacity: BufferAddress,
+    offset: BufferAddress,
+}
+
+impl BumpAllocator {
+    fn allocate(&mut self, size: BufferSize) -> Result<ArenaAllocation, BufferArenaFull> {
+        let offset = super::align_to(self.offset, BIND_BUFFER_ALIGNMENT);
+        let available = self.capacity.saturating_sub(offset);
+        if size.get() > available {
+            return Err(BufferArenaFull {
+                size: size.get(),
+                available,
+            });
+        }
+        self.offset = offset + size.get();
+        Ok(ArenaAllocation { offset, size })
+    }
+}
+
+/// Large buffer handing out ranges for many small uniforms, instead of one buffer each.
+///
+/// Ranges are allocated one after the other, each aligned to
+/// [`BIND_BUFFER_ALIGNMENT`](crate::BIND_BUFFER_ALIGNMENT), and are all freed at once by
+/// [`reset`](BufferArena::reset), typically at the start of every frame:
+///
+/// ```ignore
+/// arena.reset();
+/// for object in objects.iter() {
+///     let range = arena.allocate(object_size)?;
+///     arena.write(&queue, range, bytemuck::bytes_of(&object.uniforms));
+///     pass.set_bind_group(1, &object_bind_group, &[range.dynamic_offset()]);
+///     pass.draw(0..object.vertex_count, 0..1);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BufferArena {
+    buffer: Buffer,
+    allocator: BumpAllocator,
+}
+
+impl BufferArena {
+    /// Creates an arena of `capacity` bytes, for buffers with the given `usage`.
+    ///
+    /// [`COPY_DST`](BufferUsage::COPY_DST) is added to `usage`, for
+    /// [`write`](BufferArena::write).
+    pub fn new(device: &Device, label: Label, capacity: BufferAddress, usage: BufferUsage) -> Self {
+        let buffer = device.create_buffer(&BufferDescriptor {
+            label,
+            size: capacity,
+            usage: usage | BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            buffer,
+            allocator: BumpAllocator {
+                capacity,
+                offset: 0,
+            },
+        }
+    }
+
+    /// Allocates a range of `size` bytes after the previous ones.
+    pub fn allocate(&mut self, size: BufferSize) -> Result<ArenaAllocation, BufferArenaFull> {
+        self.allocator.allocate(size)
+    }
+
+    /// Frees all the ranges, once the commands using them were submitted.
+    pub fn reset(&mut self) {
+        self.allocator.offset = 0;
+    }
+
+    /// Schedules writing `data` at the start of `allocation`.
+    ///
+    /// # Panics
+    ///
+    /// - `data` is larger than the allocation.
+    pub fn write(&self, queue: &Queue, allocation: ArenaAllocation, data: &[u8]) {
+        assert!(
+            data.len() as BufferAddress <= allocation.size.get(),
+            "Writing {} bytes into an arena allocation of {} bytes",
+            data.len(),
+            allocation.size
+        );
+        queue.write_buffer(&self.buffer, allocation.offset, data);
+    }
+
+    /// Slice of the buffer covered by `allocation`.
+    pub fn slice(&self, allocation: ArenaAllocation) -> BufferSlice {
+        self.buffer
+            .slice(allocation.offset..allocation.offset + allocation.size.get())
+    }
+
+    /// Binding of the range covered by `allocation`.
+    pub fn binding(&self, allocation: ArenaAllocation) -> BindingResource {
+        BindingResource::Buffer(BufferBinding {
+            buffer: &self.buffer,
+            offset: allocation.offset,
+            size: Some(allocation.size),
+        })
+    }
+
+    /// Binding of `size` bytes at the start of the buffer, for a binding declared with
+    /// `has_dynamic_offset: true`, moved to each allocation with its
+    /// [`dynamic_offset`](ArenaAllocation::dynamic_offset).
+    pub fn dynamic_binding(&self, size: BufferSize) -> BindingResource {
+        BindingResource::Buffer(BufferBinding {
+            buffer: &self.buffer,
+            offset: 0,
+            size: Some(size),
+        })
+    }
+
+    /// Number of bytes allocated since the last reset, padding included.
+    pub fn used(&self) -> BufferAddress {
+        self.allocator.offset
+    }
+
+    /// Total size of the arena, in bytes.
+    pub fn capacity(&self) -> BufferAddress {
+        self.allocator.capacity
+    }
+
+    /// The underlying buffer.
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BufferArenaFull, BumpAllocator};
+    use crate::BufferSize;
+
+    fn size(size: u64) -> BufferSize {
+        BufferSize::new(size).unwrap()
+    }
+
+    #[test]
+    fn arena_allocations_are_aligned() {
+        let mut allocator = BumpAllocator {
+            capacity: 1024,
+            offset: 0,
+        };
+        let allocations = [
+            allocator.allocate(size(64)).unwrap(),
+            allocator.allocate(size(300)).unwrap(),
+            allocator.allocate(size(16)).unwrap(),
+        ];
+        let offsets: Vec<_> = allocations.iter().map(|a| a.offset).collect();
+        assert_eq!(offsets, [0, 256, 768]);
+        for pair in allocations.windows(2) {
+            assert!(pair[0].offset + pair[0].size.get() <= pair[1].offset);
+        }
+        assert_eq!(allocations[2].dynamic_offset(), 768);
+    }
+
+    #[test]
+    fn arena_full_and_reset() {
+        let mut allocator = BumpAllocator {
+            capacity: 768,
+            offset: 0,
+        };
+        allocator.allocate(size(300)).unwrap();
+        assert_eq!(
+            allocator.allocate(size(300)),
+            Err(BufferArenaFull {
+                size: 300,
+                available: 256,
+            })
+        );
+        assert_eq!(allocator.allocate(size(256)).unwrap().offset, 512);
+
+        allocator.offset = 0;
+        assert_eq!(allocator.allocate(size(300)).unwrap().offset, 0);
+    }
+}
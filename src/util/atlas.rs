@@ -0,0 +1,271 @@
+use crate::{
+    Device, Extent3d, Label, Origin3d, Queue, Texture, TextureDescriptor, TextureDimension,
+    TextureFormat, TextureUsage, TextureView,
+};
+
+/// Region of a [`TextureAtlas`] holding an inserted image.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AtlasRect {
+    /// Left edge of the image, in texels.
+    pub x: u32,
+    /// Top edge of the image, in texels.
+    pub y: u32,
+    /// Width of the image, in texels.
+    pub width: u32,
+    /// Height of the image, in texels.
+    pub height: u32,
+    /// Top left corner of the image, in normalized texture coordinates.
+    pub uv_min: [f32; 2],
+    /// Bottom right corner of the image, in normalized texture coordinates.
+    pub uv_max: [f32; 2],
+}
+
+/// Row of images sharing the same top edge.
+#[derive(Debug)]
+struct Shelf {
+    y: u32,
+    height: u32,
+    /// Left edge of the free space at the end of the shelf.
+    x: u32,
+}
+
+/// Packs rectangles into rows, each as high as the first rectangle placed on it.
+#[derive(Debug)]
+struct ShelfPacker {
+    width: u32,
+    height: u32,
+    padding: u32,
+    shelves: Vec<Shelf>,
+    /// Top edge of the free space below the last shelf.
+    next_y: u32,
+}
+
+impl ShelfPacker {
+    fn new(width: u32, height: u32, padding: u32) -> Self {
+        Self {
+            width,
+            height,
+            padding,
+            shelves: Vec::new(),
+            next_y: 0,
+        }
+    }
+
+    /// Finds room for a `width` x `height` rectangle, keeping `padding` texels free to its
+    /// right and below it. Returns its top left corner.
+    fn pack(&mut self, width: u32, height: u32) -> Option<(u32, u32)> {
+        if width > self.width || height > self.height {
+            return None;
+        }
+        let atlas_width = self.width;
+        // Pick the lowest shelf the rectangle fits on, to waste as little height as possible
+        let shelf = self
+            .shelves
+            .iter_mut()
+            .filter(|shelf| height <= shelf.height && width <= atlas_width - shelf.x)
+            .min_by_key(|shelf| shelf.height);
+        let shelf = match shelf {
+            Some(shelf) => shelf,
+            None => {
+                if height > self.height - self.next_y {
+                    return None;
+                }
+                self.shelves.push(Shelf {
+                    y: self.next_y,
+                    height,
+                    x: 0,
+                });
+                self.next_y = (self.next_y + height + self.padding).min(self.height);
+                self.shelves.last_mut().unwrap()
+            }
+        };
+        let position = (shelf.x, shelf.y);
+        shelf.x = (shelf.x + width + self.padding).min(atlas_width);
+        Some(position)
+    }
+
+    fn clear(&mut self) {
+        self.shelves.clear();
+        self.next_y = 0;
+    }
+
+    fn rect(&self, (x, y): (u32, u32), width: u32, height: u32) -> AtlasRect {
+        let (atlas_width, atlas_height) = (self.width as f32, self.height as f32);
+        AtlasRect {
+            x,
+            y,
+            width,
+            height,
+            uv_min: [x as f32 / atlas_width, y as f32 / atlas_height],
+            uv_max: [
+                (x + width) as f32 / atlas_width,
+                (y + height) as f32 / atlas_height,
+            ],
+        }
+    }
+}
+
+/// RGBA8 texture packing many small images, such as glyphs or sprites.
+///
+/// Images are placed on shelves, rows as high as the first image placed on them, with a
+/// gutter of `padding` texels between images so linear filtering doesn't bleed from one to
+/// its neighbours:
+///
+/// ```ignore
+/// let mut atlas = TextureAtlas::new(&device, Some("glyphs"), 1024, 1024, 1);
+/// let rect = atlas
+///     .insert(&queue, glyph.width, glyph.height, &glyph.rgba)
+///     .expect("Glyph atlas is full");
+/// vertices.push(GlyphVertex { uv: rect.uv_min, .. });
+/// ```
+#[derive(Debug)]
+pub struct TextureAtlas {
+    texture: Texture,
+    view: TextureView,
+    packer: ShelfPacker,
+}
+
+impl TextureAtlas {
+    /// Format of the atlas texture.
+    pub const FORMAT: TextureFormat = TextureFormat::Rgba8UnormSrgb;
+
+    /// Creates an empty `width` x `height` atlas, keeping `padding` texels between images.
+    pub fn new(device: &Device, label: Label, width: u32, height: u32, padding: u32) -> Self {
+        let texture = device.create_texture(&TextureDescriptor {
+            label,
+            size: Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: Self::FORMAT,
+            usage: TextureUsage::SAMPLED | TextureUsage::COPY_DST,
+        });
+        let view = texture.default_view();
+        Self {
+            texture,
+            view,
+            packer: ShelfPacker::new(width, height, padding),
+        }
+    }
+
+    /// Packs a `width` x `height` image and schedules uploading its tightly packed RGBA8
+    /// `data`.
+    ///
+    /// Returns `None`, uploading nothing, if the atlas has no room left for the image.
+    ///
+    /// # Panics
+    ///
+    /// - `data` is smaller than `width * height * 4` bytes.
+    pub fn insert(
+        &mut self,
+        queue: &Queue,
+        width: u32,
+        height: u32,
+        data: &[u8],
+    ) -> Option<AtlasRect> {
+        let position = self.packer.pack(width, height)?;
+        let rect = self.packer.rect(position, width, height);
+        if width > 0 && height > 0 {
+            queue.write_texture_packed(
+                self.texture.as_image_copy_mip(
+                    0,
+                    Origin3d {
+                        x: rect.x,
+                        y: rect.y,
+                        z: 0,
+                    },
+                ),
+                data,
+                Extent3d {
+                    width,
+                    height,
+                    depth_or_array_layers: 1,
+                },
+            );
+        }
+        Some(rect)
+    }
+
+    /// Forgets every inserted image, making the whole atlas available again.
+    ///
+    /// The texels are left as is, and are overwritten by the next insertions.
+    pub fn clear(&mut self) {
+        self.packer.clear();
+    }
+
+    /// The atlas texture.
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// View of the whole atlas texture, to bind for sampling.
+    pub fn view(&self) -> &TextureView {
+        &self.view
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AtlasRect, ShelfPacker};
+
+    fn overlap(a: &AtlasRect, b: &AtlasRect, padding: u32) -> bool {
+        a.x < b.x + b.width + padding
+            && b.x < a.x + a.width + padding
+            && a.y < b.y + b.height + padding
+            && b.y < a.y + a.height + padding
+    }
+
+    #[test]
+    fn atlas_rects_do_not_overlap() {
+        let padding = 2;
+        let mut packer = ShelfPacker::new(64, 64, padding);
+        let sizes = [
+            (16, 16),
+            (8, 24),
+            (30, 10),
+            (12, 12),
+            (20, 6),
+            (5, 5),
+            (40, 8),
+        ];
+        let rects: Vec<_> = sizes
+            .iter()
+            .map(|&(width, height)| {
+                let position = packer.pack(width, height).unwrap();
+                packer.rect(position, width, height)
+            })
+            .collect();
+        for (i, a) in rects.iter().enumerate() {
+            assert!(a.x + a.width <= 64 && a.y + a.height <= 64, "{:?}", a);
+            for b in &rects[i + 1..] {
+                assert!(!overlap(a, b, padding), "{:?} overlaps {:?}", a, b);
+            }
+        }
+
+        let rect = rects[2];
+        assert_eq!(rect.uv_min, [rect.x as f32 / 64.0, rect.y as f32 / 64.0]);
+        assert_eq!(
+            rect.uv_max,
+            [(rect.x + 30) as f32 / 64.0, (rect.y + 10) as f32 / 64.0]
+        );
+    }
+
+    #[test]
+    fn atlas_full_and_clear() {
+        let mut packer = ShelfPacker::new(32, 32, 1);
+        assert_eq!(packer.pack(33, 1), None);
+        assert_eq!(packer.pack(20, 20), Some((0, 0)));
+        assert_eq!(packer.pack(11, 20), Some((21, 0)));
+        assert_eq!(packer.pack(11, 11), Some((0, 21)));
+        assert_eq!(packer.pack(4, 12), None);
+        assert_eq!(packer.pack(20, 11), Some((12, 21)));
+        assert_eq!(packer.pack(1, 1), None);
+
+        packer.clear();
+        assert_eq!(packer.pack(32, 32), Some((0, 0)));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::{Buffer, BufferAddress, Queue};
+
+/// A pending write into the buffer at index `buffer` of the batch.
+#[derive(Debug, PartialEq)]
+struct Write {
+    buffer: usize,
+    offset: BufferAddress,
+    data: Vec<u8>,
+}
+
+/// Adds a write to `writes`, appending it to the latest write into the same buffer
+/// if it starts where that one ends.
+///
+/// Only the latest write of each buffer is extended, so writes to overlapping ranges
+/// still land in order.
+fn push_write(writes: &mut Vec<Write>, buffer: usize, offset: BufferAddress, data: &[u8]) {
+    if let Some(last) = writes.iter_mut().rev().find(|write| write.buffer == buffer) {
+        if last.offset + last.data.len() as BufferAddress == offset {
+            last.data.extend_from_slice(data);
+            return;
+        }
+    }
+    writes.push(Write {
+        buffer,
+        offset,
+        data: data.to_vec(),
+    });
+}
+
+/// Collects many small buffer writes, to hand them to the queue in one go.
+///
+/// Writes into adjacent ranges of the same buffer are merged into a single
+/// [`Queue::write_buffer`] call, other writes are kept in order as individual calls.
+#[derive(Debug, Default)]
+pub struct BufferUpdateBatch<'a> {
+    buffers: Vec<&'a Buffer>,
+    writes: Vec<Write>,
+}
+
+impl<'a> BufferUpdateBatch<'a> {
+    /// Creates an empty batch.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedules a write of `data` into `buffer` at `offset`.
+    ///
+    /// The same alignment rules as [`Queue::write_buffer`] apply.
+    pub fn queue(&mut self, buffer: &'a Buffer, offset: BufferAddress, data: &[u8]) {
+        if data.is_empty() {
+            return;
+        }
+        let index = match self.buffers.iter().position(|&b| std::ptr::eq(b, buffer)) {
+            Some(index) => index,
+            None => {
+                self.buffers.push(buffer);
+                self.buffers.len() - 1
+            }
+        };
+        push_write(&mut self.writes, index, offset, data);
+    }
+
+    /// Number of `write_buffer` calls the batch will make once flushed.
+    pub fn len(&self) -> usize {
+        self.writes.len()
+    }
+
+    /// Whether there are no writes scheduled.
+    pub fn is_empty(&self) -> bool {
+        self.writes.is_empty()
+    }
+
+    /// Enqueues all the scheduled writes on `queue`.
+    ///
+    /// The writes are kept, so the same batch can be flushed again, e.g. to another queue.
+    /// Use [`clear`](Self::clear) to reuse the batch for other writes.
+    pub fn flush(&self, queue: &Queue) {
+        for write in &self.writes {
+            queue.write_buffer(self.buffers[write.buffer], write.offset, &write.data);
+        }
+    }
+
+    /// Removes all the scheduled writes, keeping the allocated memory.
+    pub fn clear(&mut self) {
+        self.writes.clear();
+        self.buffers.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{push_write, Write};
+
+    #[test]
+    fn adjacent_writes_coalesce() {
+        let mut writes = Vec::new();
+        push_write(&mut writes, 0, 0, &[1, 2, 3, 4]);
+        push_write(&mut writes, 0, 4, &[5, 6, 7, 8]);
+        push_write(&mut writes, 1, 8, &[9, 9, 9, 9]);
+        assert_eq!(
+            writes,
+            [
+                Write {
+                    buffer: 0,
+                    offset: 0,
+                    data: vec![1, 2, 3, 4, 5, 6, 7, 8],
+                },
+                Write {
+                    buffer: 1,
+                    offset: 8,
+                    data: vec![9, 9, 9, 9],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn non_adjacent_writes_stay_separate() {
+        let mut writes = Vec::new();
+        push_write(&mut writes, 0, 0, &[1; 4]);
+        // Adjacent, but to another buffer
+        push_write(&mut writes, 1, 4, &[2; 4]);
+        // Same buffer, but with a gap
+        push_write(&mut writes, 0, 16, &[3; 4]);
+        // Adjacent to the first write, which is no longer the latest of its buffer
+        push_write(&mut writes, 0, 4, &[4; 4]);
+        assert_eq!(writes.len(), 4);
+        assert_eq!(writes[3].offset, 4);
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn flushed_writes_read_back() {
+        use super::BufferUpdateBatch;
+        use crate::util::{read_buffer, test_device};
+
+        let (device, queue) = match test_device() {
+            Some(device) => device,
+            None => return,
+        };
+        let buffer = device.create_buffer(&crate::BufferDescriptor {
+            label: None,
+            size: 16,
+            usage: crate::BufferUsage::COPY_DST | crate::BufferUsage::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut batch = BufferUpdateBatch::new();
+        batch.queue(&buffer, 0, &[1; 4]);
+        batch.queue(&buffer, 4, &[2; 4]);
+        batch.queue(&buffer, 12, &[3; 4]);
+        assert_eq!(batch.len(), 2);
+        batch.flush(&queue);
+        queue.submit(None);
+
+        let contents = pollster::block_on(read_buffer(&device, &buffer, ..)).unwrap();
+        assert_eq!(contents, [1, 1, 1, 1, 2, 2, 2, 2, 0, 0, 0, 0, 3, 3, 3, 3]);
+    }
+}
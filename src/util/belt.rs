@@ -1,7 +1,8 @@
 use crate::{
     Buffer, BufferAddress, BufferDescriptor, BufferSize, BufferUsage, BufferViewMut,
-    CommandEncoder, Device, MapMode,
+    CommandEncoder, Device, Extent3d, ImageCopyBuffer, ImageCopyTexture, ImageDataLayout, MapMode,
 };
+use std::num::NonZeroU32;
 use std::pin::Pin;
 use std::task::{self, Poll};
 use std::{future::Future, sync::mpsc};
@@ -44,6 +45,17 @@ struct Chunk {
     offset: BufferAddress,
 }
 
+/// Memory pooled by a [`StagingBelt`], as returned by [`StagingBelt::stats`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StagingBeltStats {
+    /// Number of chunks ready to be reused.
+    pub free_chunks: usize,
+    /// Total size of the chunks ready to be reused, in bytes.
+    pub free_bytes: BufferAddress,
+    /// Size of newly allocated chunks, as passed to [`StagingBelt::new`].
+    pub chunk_size: BufferAddress,
+}
+
 /// Staging belt is a machine that uploads data.
 ///
 /// Internally it uses a ring-buffer of staging buffers that are sub-allocated.
@@ -51,9 +63,9 @@ struct Chunk {
 /// which you can fill to avoid an extra data copy.
 ///
 /// Using a staging belt is slightly complicated, and generally goes as follows:
-/// - Write to buffers that need writing to using `write_buffer`.
+/// - Write to buffers and textures that need writing to using `write_buffer` and `write_texture`.
 /// - Call `finish`.
-/// - Submit all command encoders used with `write_buffer`.
+/// - Submit all command encoders used with `write_buffer` and `write_texture`.
 /// - Call `recall`
 pub struct StagingBelt {
     chunk_size: BufferAddress,
@@ -63,6 +75,8 @@ pub struct StagingBelt {
     closed_chunks: Vec<Chunk>,
     /// Chunks that are back from the GPU and ready to be used.
     free_chunks: Vec<Chunk>,
+    /// Number of free chunks above which `recall` drops the excess, if any.
+    max_free_chunks: Option<usize>,
     sender: mpsc::Sender<Chunk>,
     receiver: mpsc::Receiver<Chunk>,
 }
@@ -80,11 +94,33 @@ impl StagingBelt {
             active_chunks: Vec::new(),
             closed_chunks: Vec::new(),
             free_chunks: Vec::new(),
+            max_free_chunks: None,
             sender,
             receiver,
         }
     }
 
+    /// Limit the number of free chunks kept around for reuse.
+    ///
+    /// When more chunks than `max_free_chunks` are free, `recall` releases the largest ones,
+    /// so a spike of uploads doesn't keep its memory allocated forever. `None`, the default,
+    /// keeps every chunk.
+    pub fn set_max_free_chunks(&mut self, max_free_chunks: Option<usize>) {
+        self.max_free_chunks = max_free_chunks;
+    }
+
+    /// Report the memory pooled for reuse.
+    ///
+    /// Chunks are only counted as free once they were collected by a call to `recall`
+    /// made after the GPU released them.
+    pub fn stats(&self) -> StagingBeltStats {
+        StagingBeltStats {
+            free_chunks: self.free_chunks.len(),
+            free_bytes: self.free_chunks.iter().map(|chunk| chunk.size).sum(),
+            chunk_size: self.chunk_size,
+        }
+    }
+
     /// Allocate the staging belt slice of `size` to be uploaded into the `target` buffer
     /// at the specified offset.
     ///
@@ -98,20 +134,76 @@ impl StagingBelt {
         size: BufferSize,
         device: &Device,
     ) -> BufferViewMut {
-        let mut chunk = if let Some(index) = self
+        let mut chunk = self.take_chunk(size.get(), 1, device);
+
+        encoder.copy_buffer_to_buffer(&chunk.buffer, chunk.offset, target, offset, size.get());
+        self.push_active_chunk(chunk, size.get())
+    }
+
+    /// Allocate the staging belt slice to be uploaded into the `target` texture.
+    ///
+    /// `data_layout.bytes_per_row` is rounded up to [`COPY_BYTES_PER_ROW_ALIGNMENT`](crate::COPY_BYTES_PER_ROW_ALIGNMENT),
+    /// so consecutive rows in the returned slice are that rounded amount of bytes apart.
+    /// `data_layout.offset` is the amount of bytes at the start of the slice that are skipped.
+    /// If `data_layout.rows_per_image` is `None`, it defaults to `size.height`.
+    ///
+    /// The upload will be placed into the provided command encoder. This encoder
+    /// must be submitted after `finish` is called and before `recall` is called.
+    ///
+    /// # Panics
+    ///
+    /// - `data_layout.bytes_per_row` is `None`.
+    pub fn write_texture(
+        &mut self,
+        encoder: &mut CommandEncoder,
+        target: ImageCopyTexture,
+        data_layout: ImageDataLayout,
+        size: Extent3d,
+        device: &Device,
+    ) -> BufferViewMut {
+        let (padded_bytes_per_row, rows_per_image, upload_size) =
+            texture_upload_layout(&data_layout, size);
+
+        // Buffer offsets of texture copies have to be aligned to the texel block size,
+        // which the row alignment is always a multiple of.
+        let offset_alignment = crate::COPY_BYTES_PER_ROW_ALIGNMENT as BufferAddress;
+        let mut chunk = self.take_chunk(upload_size, offset_alignment, device);
+        chunk.offset = super::align_to(chunk.offset, offset_alignment);
+
+        encoder.copy_buffer_to_texture(
+            ImageCopyBuffer {
+                buffer: &chunk.buffer,
+                layout: ImageDataLayout {
+                    offset: chunk.offset + data_layout.offset,
+                    bytes_per_row: NonZeroU32::new(padded_bytes_per_row),
+                    rows_per_image: NonZeroU32::new(rows_per_image),
+                },
+            },
+            target,
+            size,
+        );
+        self.push_active_chunk(chunk, upload_size)
+    }
+
+    /// Find an active or free chunk that can fit `size` bytes at an offset aligned to `alignment`,
+    /// or allocate a new one.
+    fn take_chunk(
+        &mut self,
+        size: BufferAddress,
+        alignment: BufferAddress,
+        device: &Device,
+    ) -> Chunk {
+        let align_mask = alignment - 1;
+        if let Some(index) = self
             .active_chunks
             .iter()
-            .position(|chunk| chunk.offset + size.get() <= chunk.size)
+            .position(|chunk| ((chunk.offset + align_mask) & !align_mask) + size <= chunk.size)
         {
             self.active_chunks.swap_remove(index)
-        } else if let Some(index) = self
-            .free_chunks
-            .iter()
-            .position(|chunk| size.get() <= chunk.size)
-        {
+        } else if let Some(index) = self.free_chunks.iter().position(|chunk| size <= chunk.size) {
             self.free_chunks.swap_remove(index)
         } else {
-            let size = self.chunk_size.max(size.get());
+            let size = self.chunk_size.max(size);
             Chunk {
                 buffer: device.create_buffer(&BufferDescriptor {
                     label: Some("staging"),
@@ -122,11 +214,13 @@ impl StagingBelt {
                 size,
                 offset: 0,
             }
-        };
+        }
+    }
 
-        encoder.copy_buffer_to_buffer(&chunk.buffer, chunk.offset, target, offset, size.get());
+    /// Reserve `size` bytes at the current offset of `chunk`, and return them as a mapped view.
+    fn push_active_chunk(&mut self, mut chunk: Chunk, size: BufferAddress) -> BufferViewMut {
         let old_offset = chunk.offset;
-        chunk.offset += size.get();
+        chunk.offset += size;
         let remainder = chunk.offset % crate::MAP_ALIGNMENT;
         if remainder != 0 {
             chunk.offset += crate::MAP_ALIGNMENT - remainder;
@@ -137,7 +231,7 @@ impl StagingBelt {
             .last()
             .unwrap()
             .buffer
-            .slice(old_offset..old_offset + size.get())
+            .slice(old_offset..old_offset + size)
             .get_mapped_range_mut()
     }
 
@@ -155,11 +249,17 @@ impl StagingBelt {
     /// Recall all of the closed buffers back to be reused.
     ///
     /// This has to be called after the command encoders written to `write_buffer` are submitted!
+    ///
+    /// Free chunks above the limit set with [`set_max_free_chunks`](StagingBelt::set_max_free_chunks)
+    /// are released.
     pub fn recall(&mut self) -> impl Future<Output = ()> + Send {
         while let Ok(mut chunk) = self.receiver.try_recv() {
             chunk.offset = 0;
             self.free_chunks.push(chunk);
         }
+        trim_free_chunks(&mut self.free_chunks, self.max_free_chunks, |chunk| {
+            chunk.size
+        });
 
         let sender = &self.sender;
         let futures = self
@@ -182,3 +282,123 @@ impl StagingBelt {
         Join { futures }
     }
 }
+
+/// Compute the padded bytes per row, rows per image and total size in bytes of the staging
+/// slice `StagingBelt::write_texture` uploads from.
+fn texture_upload_layout(
+    data_layout: &ImageDataLayout,
+    size: Extent3d,
+) -> (u32, u32, BufferAddress) {
+    let row_align_mask = crate::COPY_BYTES_PER_ROW_ALIGNMENT - 1;
+    let bytes_per_row = data_layout
+        .bytes_per_row
+        .expect("bytes_per_row is required to write a texture through the staging belt")
+        .get();
+    let padded_bytes_per_row = (bytes_per_row + row_align_mask) & !row_align_mask;
+    let rows_per_image = data_layout
+        .rows_per_image
+        .map_or(size.height, |rows| rows.get());
+    let upload_size = data_layout.offset
+        + padded_bytes_per_row as BufferAddress
+            * rows_per_image as BufferAddress
+            * size.depth_or_array_layers as BufferAddress;
+    (padded_bytes_per_row, rows_per_image, upload_size)
+}
+
+/// Keep at most `max_free_chunks` of `chunks`, releasing the largest ones, as measured by `size`.
+fn trim_free_chunks<T>(
+    chunks: &mut Vec<T>,
+    max_free_chunks: Option<usize>,
+    size: impl Fn(&T) -> BufferAddress,
+) {
+    if let Some(max_free_chunks) = max_free_chunks {
+        if chunks.len() > max_free_chunks {
+            // Oversized chunks come from the largest uploads, release those first
+            chunks.sort_by_key(size);
+            chunks.truncate(max_free_chunks);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{texture_upload_layout, trim_free_chunks, StagingBelt, StagingBeltStats};
+    use crate::{Extent3d, ImageDataLayout};
+    use std::num::NonZeroU32;
+
+    #[test]
+    fn stats_of_new_belt() {
+        let mut belt = StagingBelt::new(1024);
+        belt.set_max_free_chunks(Some(2));
+        // Nothing to recall, so the pool stays empty
+        drop(belt.recall());
+        assert_eq!(
+            belt.stats(),
+            StagingBeltStats {
+                free_chunks: 0,
+                free_bytes: 0,
+                chunk_size: 1024,
+            }
+        );
+    }
+
+    #[test]
+    fn trim_free_chunks_keeps_smallest() {
+        let trim = |sizes: &[u64], max_free_chunks| {
+            let mut sizes = sizes.to_vec();
+            trim_free_chunks(&mut sizes, max_free_chunks, |&size| size);
+            sizes
+        };
+
+        assert_eq!(
+            trim(&[4096, 1024, 65536, 1024], None),
+            [4096, 1024, 65536, 1024]
+        );
+        assert_eq!(trim(&[4096, 1024, 65536, 1024], Some(2)), [1024, 1024]);
+        assert_eq!(trim(&[4096, 1024, 65536], Some(3)), [4096, 1024, 65536]);
+        assert_eq!(trim(&[4096, 1024, 65536], Some(5)), [4096, 1024, 65536]);
+        assert_eq!(trim(&[4096, 1024], Some(0)), [] as [u64; 0]);
+        assert_eq!(trim(&[], Some(1)), [] as [u64; 0]);
+    }
+
+    #[test]
+    fn texture_upload_layout_pads_rows() {
+        let size = Extent3d {
+            width: 100,
+            height: 3,
+            depth_or_array_layers: 2,
+        };
+        let layout = |offset, bytes_per_row, rows_per_image| ImageDataLayout {
+            offset,
+            bytes_per_row: NonZeroU32::new(bytes_per_row),
+            rows_per_image: NonZeroU32::new(rows_per_image),
+        };
+
+        // 100 Rgba8 texels are 400 bytes, padded up to 512
+        assert_eq!(
+            texture_upload_layout(&layout(0, 400, 0), size),
+            (512, 3, 512 * 3 * 2)
+        );
+        // Already aligned rows are left alone, and the offset is part of the size
+        assert_eq!(
+            texture_upload_layout(&layout(16, 256, 0), size),
+            (256, 3, 16 + 256 * 3 * 2)
+        );
+        // Explicit rows per image override the height
+        assert_eq!(
+            texture_upload_layout(&layout(0, 1, 4), size),
+            (256, 4, 256 * 4 * 2)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn texture_upload_layout_requires_bytes_per_row() {
+        let size = Extent3d {
+            width: 1,
+            height: 1,
+            depth_or_array_layers: 1,
+        };
+        texture_upload_layout(&ImageDataLayout::default(), size);
+    }
+}
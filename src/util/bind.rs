@@ -0,0 +1,140 @@
+use crate::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, Device, Label, ShaderStage,
+};
+
+/// Bind group layout and a bind group created from it, declared together.
+///
+/// Each binding is given once, with both its layout and its resource, so the bind group
+/// can't disagree with its layout about the binding numbers:
+///
+/// ```ignore
+/// let pair = BindGroupPair::new(&device, Some("material"), vec![
+///     (0, wgpu::ShaderStage::VERTEX, uniform_type, uniforms.as_entire_binding()),
+///     (1, wgpu::ShaderStage::FRAGMENT, texture_type, wgpu::BindingResource::TextureView(&view)),
+///     (2, wgpu::ShaderStage::FRAGMENT, sampler_type, wgpu::BindingResource::Sampler(&sampler)),
+/// ]);
+/// pass.set_bind_group(0, &pair.bind_group, &[]);
+/// ```
+#[derive(Debug)]
+pub struct BindGroupPair {
+    /// Layout of the bind group, for pipeline layouts using it.
+    pub layout: BindGroupLayout,
+    /// Bind group holding the resources.
+    pub bind_group: BindGroup,
+}
+
+impl BindGroupPair {
+    /// Creates the layout and the bind group from `(binding, visibility, type, resource)`
+    /// entries. Both are labeled with `label`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a binding number is declared twice.
+    pub fn new(
+        device: &Device,
+        label: Label,
+        entries: Vec<(u32, ShaderStage, BindingType, BindingResource)>,
+    ) -> Self {
+        let (layout_entries, entries) = split_entries(entries);
+        let layout = device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            label,
+            entries: &layout_entries,
+        });
+        let bind_group = device.create_bind_group(&BindGroupDescriptor {
+            label,
+            layout: &layout,
+            entries: &entries,
+        });
+        Self { layout, bind_group }
+    }
+}
+
+fn split_entries(
+    entries: Vec<(u32, ShaderStage, BindingType, BindingResource)>,
+) -> (Vec<BindGroupLayoutEntry>, Vec<BindGroupEntry>) {
+    let mut layout_entries = Vec::<BindGroupLayoutEntry>::with_capacity(entries.len());
+    let mut bind_group_entries = Vec::with_capacity(entries.len());
+    for (binding, visibility, ty, resource) in entries {
+        assert!(
+            layout_entries.iter().all(|entry| entry.binding != binding),
+            "Binding {} is declared twice",
+            binding
+        );
+        layout_entries.push(BindGroupLayoutEntry {
+            binding,
+            visibility,
+            ty,
+            count: None,
+        });
+        bind_group_entries.push(BindGroupEntry { binding, resource });
+    }
+    (layout_entries, bind_group_entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_entries;
+    use crate::{BindingResource, BindingType, BufferBindingType, ShaderStage};
+
+    const UNIFORM: BindingType = BindingType::Buffer {
+        ty: BufferBindingType::Uniform,
+        has_dynamic_offset: false,
+        min_binding_size: None,
+    };
+    const SAMPLER: BindingType = BindingType::Sampler {
+        filtering: true,
+        comparison: false,
+    };
+
+    #[test]
+    fn bind_group_pair_entries_line_up() {
+        let (layout_entries, entries) = split_entries(vec![
+            (
+                0,
+                ShaderStage::VERTEX,
+                UNIFORM,
+                BindingResource::BufferArray(&[]),
+            ),
+            (
+                2,
+                ShaderStage::FRAGMENT,
+                SAMPLER,
+                BindingResource::TextureViewArray(&[]),
+            ),
+        ]);
+        assert_eq!(layout_entries.len(), 2);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(layout_entries[0].binding, 0);
+        assert_eq!(layout_entries[0].visibility, ShaderStage::VERTEX);
+        assert_eq!(layout_entries[0].ty, UNIFORM);
+        assert_eq!(layout_entries[0].count, None);
+        assert_eq!(entries[0].binding, 0);
+        assert_eq!(layout_entries[1].binding, 2);
+        assert_eq!(layout_entries[1].ty, SAMPLER);
+        assert_eq!(entries[1].binding, 2);
+        assert!(matches!(
+            entries[1].resource,
+            BindingResource::TextureViewArray(_)
+        ));
+    }
+
+    #[test]
+    #[should_panic(expected = "Binding 1 is declared twice")]
+    fn bind_group_pair_duplicate_binding() {
+        split_entries(vec![
+            (
+                1,
+                ShaderStage::VERTEX,
+                UNIFORM,
+                BindingResource::BufferArray(&[]),
+            ),
+            (
+                1,
+                ShaderStage::FRAGMENT,
+                SAMPLER,
+                BindingResource::TextureViewArray(&[]),
+            ),
+        ]);
+    }
+}
@@ -0,0 +1,65 @@
+use wgt::{BlendComponent, BlendFactor, BlendOperation, BlendState};
+
+const fn add(src_factor: BlendFactor, dst_factor: BlendFactor) -> BlendComponent {
+    BlendComponent {
+        src_factor,
+        dst_factor,
+        operation: BlendOperation::Add,
+    }
+}
+
+/// Blend presets beyond `BlendState::REPLACE`, `BlendState::ALPHA_BLENDING` and
+/// `BlendState::PREMULTIPLIED_ALPHA_BLENDING`.
+///
+/// In the formulas, `src` is the fragment output and `dst` the color already in the target.
+pub trait BlendStateExt {
+    /// Adds the fragment to the target: `src + dst`, for both color and alpha.
+    const ADDITIVE: Self;
+
+    /// Multiplies the target by the fragment: `src * dst`, for both color and alpha.
+    const MULTIPLY: Self;
+}
+
+impl BlendStateExt for BlendState {
+    const ADDITIVE: Self = Self {
+        color: add(BlendFactor::One, BlendFactor::One),
+        alpha: add(BlendFactor::One, BlendFactor::One),
+    };
+
+    const MULTIPLY: Self = Self {
+        color: add(BlendFactor::Dst, BlendFactor::Zero),
+        alpha: add(BlendFactor::DstAlpha, BlendFactor::Zero),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BlendStateExt;
+    use wgt::{BlendFactor, BlendOperation, BlendState};
+
+    fn factors(state: BlendState) -> [(BlendFactor, BlendFactor); 2] {
+        assert_eq!(state.color.operation, BlendOperation::Add);
+        assert_eq!(state.alpha.operation, BlendOperation::Add);
+        [
+            (state.color.src_factor, state.color.dst_factor),
+            (state.alpha.src_factor, state.alpha.dst_factor),
+        ]
+    }
+
+    #[test]
+    fn additive() {
+        let sum = (BlendFactor::One, BlendFactor::One);
+        assert_eq!(factors(BlendState::ADDITIVE), [sum, sum]);
+    }
+
+    #[test]
+    fn multiply() {
+        assert_eq!(
+            factors(BlendState::MULTIPLY),
+            [
+                (BlendFactor::Dst, BlendFactor::Zero),
+                (BlendFactor::DstAlpha, BlendFactor::Zero),
+            ]
+        );
+    }
+}
@@ -0,0 +1,9 @@
+[[group(0), binding(0)]]
+var r_color: texture_2d<f32>;
+[[group(0), binding(1)]]
+var r_sampler: sampler;
+
+[[stage(fragment)]]
+fn fs_main([[location(0)]] tex_coords: vec2<f32>) -> [[location(0)]] vec4<f32> {
+    return textureSample(r_color, r_sampler, tex_coords);
+}
@@ -0,0 +1,212 @@
+/// Attachment formats a render bundle is recorded for.
+///
+/// Render passes executing the bundle must use the exact same formats and sample count,
+/// which [`assert_matches`](RenderBundleTargets::assert_matches) checks up front instead
+/// of failing validation at draw time.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RenderBundleTargets {
+    /// Formats of the color attachments.
+    pub color_formats: Vec<crate::TextureFormat>,
+    /// Format of the depth stencil attachment, if any.
+    pub depth_stencil_format: Option<crate::TextureFormat>,
+    /// Sample count of all the attachments.
+    pub sample_count: u32,
+}
+
+impl RenderBundleTargets {
+    /// Unlabeled descriptor of a render bundle encoder for these targets.
+    pub fn descriptor(&self) -> crate::RenderBundleEncoderDescriptor {
+        crate::RenderBundleEncoderDescriptor {
+            label: None,
+            color_formats: &self.color_formats,
+            depth_stencil_format: self.depth_stencil_format,
+            sample_count: self.sample_count,
+        }
+    }
+
+    /// Returns true if a render pass with the given attachments can execute the bundle.
+    pub fn matches(
+        &self,
+        color_formats: &[crate::TextureFormat],
+        depth_stencil_format: Option<crate::TextureFormat>,
+        sample_count: u32,
+    ) -> bool {
+        self.color_formats == color_formats
+            && self.depth_stencil_format == depth_stencil_format
+            && self.sample_count == sample_count
+    }
+
+    /// Panics if a render pass with the given attachments can't execute the bundle.
+    pub fn assert_matches(
+        &self,
+        color_formats: &[crate::TextureFormat],
+        depth_stencil_format: Option<crate::TextureFormat>,
+        sample_count: u32,
+    ) {
+        assert!(
+            self.matches(color_formats, depth_stencil_format, sample_count),
+            "Render bundle targets {:?} don't match the render pass color formats {:?}, \
+            depth stencil format {:?} and sample count {}",
+            self,
+            color_formats,
+            depth_stencil_format,
+            sample_count
+        );
+    }
+}
+
+/// Records a [`RenderBundle`](crate::RenderBundle), remembering the formats it targets.
+#[derive(Debug)]
+pub struct RenderBundleBuilder<'a> {
+    encoder: crate::RenderBundleEncoder<'a>,
+    targets: RenderBundleTargets,
+}
+
+impl<'a> RenderBundleBuilder<'a> {
+    /// Creates an encoder recording a bundle for `targets`.
+    pub fn new(device: &'a crate::Device, targets: RenderBundleTargets) -> Self {
+        Self {
+            encoder: device.create_render_bundle_encoder(&targets.descriptor()),
+            targets,
+        }
+    }
+
+    /// Encoder to record the draws of the bundle with.
+    pub fn encoder(&mut self) -> &mut crate::RenderBundleEncoder<'a> {
+        &mut self.encoder
+    }
+
+    /// Formats the bundle is recorded for.
+    pub fn targets(&self) -> &RenderBundleTargets {
+        &self.targets
+    }
+
+    /// Finishes recording, returning the bundle.
+    pub fn finish(self, label: crate::Label) -> crate::RenderBundle {
+        self.encoder
+            .finish(&crate::RenderBundleDescriptor { label })
+    }
+}
+
+/// Records render bundles for the same targets on several threads at once.
+///
+/// A [`RenderPass`](crate::RenderPass) mutably borrows its command encoder, so its commands
+/// can only be recorded on one thread. Bundle encoders only borrow the device, so each job
+/// gets its own encoder on its own thread, and the resulting bundles are then executed in
+/// order on a single pass with [`execute_bundles`](crate::RenderPass::execute_bundles):
+///
+/// ```no_run
+/// # fn example<'a>(
+/// #     device: &'a wgpu::Device,
+/// #     targets: wgpu::util::RenderBundleTargets,
+/// #     opaque: &'a wgpu::RenderPipeline,
+/// #     transparent: &'a wgpu::RenderPipeline,
+/// # ) -> Vec<wgpu::RenderBundle> {
+/// let recorder = wgpu::util::ParallelBundleRecorder::new(device, targets);
+/// recorder.record(vec![
+///     Box::new(|encoder: &mut wgpu::RenderBundleEncoder<'a>| {
+///         encoder.set_pipeline(opaque);
+///         encoder.draw(0..3, 0..1);
+///     }),
+///     Box::new(|encoder: &mut wgpu::RenderBundleEncoder<'a>| {
+///         encoder.set_pipeline(transparent);
+///         encoder.draw(0..6, 0..2);
+///     }),
+/// ])
+/// # }
+/// ```
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub struct ParallelBundleRecorder<'a> {
+    device: &'a crate::Device,
+    targets: RenderBundleTargets,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<'a> ParallelBundleRecorder<'a> {
+    /// Creates a recorder of bundles for `targets`.
+    pub fn new(device: &'a crate::Device, targets: RenderBundleTargets) -> Self {
+        Self { device, targets }
+    }
+
+    /// Formats the bundles are recorded for.
+    pub fn targets(&self) -> &RenderBundleTargets {
+        &self.targets
+    }
+
+    /// Runs every job on its own thread, each recording into a fresh bundle encoder.
+    ///
+    /// Jobs are boxed, so each can be a different closure. Blocks until all the jobs are
+    /// done, and returns the bundles in the order of `jobs`. A panic in a job is resumed on
+    /// the calling thread.
+    pub fn record<'j>(
+        &self,
+        jobs: Vec<Box<dyn FnOnce(&mut crate::RenderBundleEncoder<'a>) + Send + 'j>>,
+    ) -> Vec<crate::RenderBundle> {
+        let device = self.device;
+        let targets = &self.targets;
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = jobs
+                .into_iter()
+                .map(|job| {
+                    scope.spawn(move || {
+                        let mut builder = RenderBundleBuilder::new(device, targets.clone());
+                        job(builder.encoder());
+                        builder.finish(None)
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|payload| std::panic::resume_unwind(payload))
+                })
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderBundleTargets;
+    use crate::TextureFormat;
+
+    #[test]
+    fn render_bundle_targets_descriptor() {
+        let targets = RenderBundleTargets {
+            color_formats: vec![TextureFormat::Bgra8UnormSrgb],
+            depth_stencil_format: Some(TextureFormat::Depth32Float),
+            sample_count: 1,
+        };
+        let desc = targets.descriptor();
+        assert_eq!(desc.label, None);
+        assert_eq!(desc.color_formats, &[TextureFormat::Bgra8UnormSrgb]);
+        assert_eq!(desc.depth_stencil_format, Some(TextureFormat::Depth32Float));
+        assert_eq!(desc.sample_count, 1);
+
+        assert!(targets.matches(
+            &[TextureFormat::Bgra8UnormSrgb],
+            Some(TextureFormat::Depth32Float),
+            1
+        ));
+        assert!(!targets.matches(&[TextureFormat::Bgra8UnormSrgb], None, 1));
+        assert!(!targets.matches(
+            &[TextureFormat::Rgba8Unorm],
+            Some(TextureFormat::Depth32Float),
+            1
+        ));
+    }
+
+    #[test]
+    #[should_panic]
+    fn render_bundle_targets_mismatch_panics() {
+        let targets = RenderBundleTargets {
+            color_formats: vec![TextureFormat::Rgba8Unorm],
+            depth_stencil_format: None,
+            sample_count: 4,
+        };
+        targets.assert_matches(&[TextureFormat::Rgba8Unorm], None, 1);
+    }
+}
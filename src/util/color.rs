@@ -0,0 +1,82 @@
+use wgt::Color;
+
+/// Converts an 8-bit sRGB encoded channel to linear.
+fn srgb_to_linear(channel: u8) -> f64 {
+    let c = channel as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Constructors of [`Color`] from the representations engines commonly use.
+///
+/// A clear color is interpreted in the space of the attachment: for `*Srgb` formats it is
+/// a linear color, which gets encoded to sRGB when stored, while other formats store it
+/// as is.
+pub trait ColorExt: Sized {
+    /// Color with the given components.
+    fn from_rgba_f32(rgba: [f32; 4]) -> Self;
+
+    /// Linear color of an sRGB hex code such as `0xFF8000FF`, laid out as `0xRRGGBBAA`.
+    ///
+    /// The color channels are converted from sRGB to linear, so clearing an `*Srgb`
+    /// attachment stores the original code. Alpha is linear and only rescaled.
+    fn from_srgb_hex(rgba: u32) -> Self;
+}
+
+impl ColorExt for Color {
+    fn from_rgba_f32(rgba: [f32; 4]) -> Self {
+        let [r, g, b, a] = rgba;
+        Self {
+            r: r as f64,
+            g: g as f64,
+            b: b as f64,
+            a: a as f64,
+        }
+    }
+
+    fn from_srgb_hex(rgba: u32) -> Self {
+        let [r, g, b, a] = rgba.to_be_bytes();
+        Self {
+            r: srgb_to_linear(r),
+            g: srgb_to_linear(g),
+            b: srgb_to_linear(b),
+            a: a as f64 / 255.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ColorExt;
+    use wgt::Color;
+
+    #[test]
+    fn color_from_rgba_f32() {
+        let color = Color::from_rgba_f32([0.25, 0.5, 1.0, 0.0]);
+        assert_eq!(
+            color,
+            Color {
+                r: 0.25,
+                g: 0.5,
+                b: 1.0,
+                a: 0.0,
+            }
+        );
+    }
+
+    #[test]
+    fn color_from_srgb_hex() {
+        assert_eq!(Color::from_srgb_hex(0x000000FF), Color::BLACK);
+        assert_eq!(Color::from_srgb_hex(0xFFFFFFFF), Color::WHITE);
+
+        // sRGB 0x80 is about 21.6% linear intensity
+        let color = Color::from_srgb_hex(0x80_40_08_80);
+        assert!((color.r - 0.2158605).abs() < 1e-6);
+        assert!((color.g - 0.0512695).abs() < 1e-6);
+        assert!((color.b - 0.0024282).abs() < 1e-6);
+        assert!((color.a - 128.0 / 255.0).abs() < 1e-9);
+    }
+}
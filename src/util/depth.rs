@@ -0,0 +1,80 @@
+use wgt::{CompareFunction, DepthBiasState, DepthStencilState, StencilState, TextureFormat};
+
+/// Presets for the common [`DepthStencilState`] setups.
+///
+/// Both leave the stencil test disabled and use no depth bias. The raw struct remains
+/// available for custom stencil work.
+pub trait DepthStencilStateExt: Sized {
+    /// Depth test with [`CompareFunction::Less`], writing depth.
+    fn depth_write(format: TextureFormat) -> Self;
+
+    /// Depth test with [`CompareFunction::LessEqual`], without writing depth.
+    ///
+    /// Useful for passes drawn on top of a depth prepass, or for transparent objects.
+    fn depth_read_only(format: TextureFormat) -> Self;
+}
+
+impl DepthStencilStateExt for DepthStencilState {
+    fn depth_write(format: TextureFormat) -> Self {
+        Self {
+            format,
+            depth_write_enabled: true,
+            depth_compare: CompareFunction::Less,
+            stencil: StencilState::default(),
+            bias: DepthBiasState::default(),
+        }
+    }
+
+    fn depth_read_only(format: TextureFormat) -> Self {
+        Self {
+            format,
+            depth_write_enabled: false,
+            depth_compare: CompareFunction::LessEqual,
+            stencil: StencilState::default(),
+            bias: DepthBiasState::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DepthStencilStateExt;
+    use wgt::{
+        CompareFunction, DepthStencilState, StencilFaceState, StencilOperation, TextureFormat,
+    };
+
+    fn assert_stencil_disabled(state: &DepthStencilState) {
+        for face in &[state.stencil.front, state.stencil.back] {
+            assert_eq!(
+                *face,
+                StencilFaceState {
+                    compare: CompareFunction::Always,
+                    fail_op: StencilOperation::Keep,
+                    depth_fail_op: StencilOperation::Keep,
+                    pass_op: StencilOperation::Keep,
+                }
+            );
+        }
+        assert_eq!(state.bias.constant, 0);
+        assert_eq!(state.bias.slope_scale, 0.0);
+        assert_eq!(state.bias.clamp, 0.0);
+    }
+
+    #[test]
+    fn depth_write_preset() {
+        let state = DepthStencilState::depth_write(TextureFormat::Depth32Float);
+        assert_eq!(state.format, TextureFormat::Depth32Float);
+        assert!(state.depth_write_enabled);
+        assert_eq!(state.depth_compare, CompareFunction::Less);
+        assert_stencil_disabled(&state);
+    }
+
+    #[test]
+    fn depth_read_only_preset() {
+        let state = DepthStencilState::depth_read_only(TextureFormat::Depth24Plus);
+        assert_eq!(state.format, TextureFormat::Depth24Plus);
+        assert!(!state.depth_write_enabled);
+        assert_eq!(state.depth_compare, CompareFunction::LessEqual);
+        assert_stencil_disabled(&state);
+    }
+}
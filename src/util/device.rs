@@ -17,6 +17,19 @@ pub trait DeviceExt {
     /// Creates a [Buffer](crate::Buffer) with data to initialize it.
     fn create_buffer_init(&self, desc: &BufferInitDescriptor) -> crate::Buffer;
 
+    /// Creates a [Buffer](crate::Buffer) with data to initialize it, with its size rounded
+    /// up to a multiple of `alignment`.
+    ///
+    /// The contents are copied to the start of the buffer and the padding is zeroed.
+    /// The buffer is at least `alignment` bytes, even for empty contents. `alignment` has
+    /// to be a power of two, and is raised to [`COPY_BUFFER_ALIGNMENT`](crate::COPY_BUFFER_ALIGNMENT)
+    /// if smaller.
+    fn create_buffer_init_aligned(
+        &self,
+        desc: &BufferInitDescriptor,
+        alignment: crate::BufferAddress,
+    ) -> crate::Buffer;
+
     /// Upload an entire texture and its mipmaps from a source buffer.
     ///
     /// Expects all mipmaps to be tightly packed in the data buffer.
@@ -26,12 +39,72 @@ pub trait DeviceExt {
     ///
     /// Example:
     /// Layer0Mip0 Layer0Mip1 Layer0Mip2 ... Layer1Mip0 Layer1Mip1 Layer1Mip2 ...
+    ///
+    /// Rows are expected to be tightly packed as well. Unlike buffer to texture copies,
+    /// [`Queue::write_texture`](crate::Queue::write_texture) does not require rows to be
+    /// padded to [`COPY_BYTES_PER_ROW_ALIGNMENT`](crate::COPY_BYTES_PER_ROW_ALIGNMENT).
+    ///
+    /// # Panics
+    ///
+    /// - `data` is smaller than the texture described by `desc`.
     fn create_texture_with_data(
         &self,
         queue: &crate::Queue,
         desc: &crate::TextureDescriptor,
         data: &[u8],
     ) -> crate::Texture;
+
+    /// Creates an unlabeled [PipelineLayout](crate::PipelineLayout) from the given
+    /// bind group layouts, without push constants.
+    fn create_pipeline_layout_simple(
+        &self,
+        bind_group_layouts: &[&crate::BindGroupLayout],
+    ) -> crate::PipelineLayout;
+
+    /// Creates an unlabeled [PipelineLayout](crate::PipelineLayout) from the given
+    /// bind group layouts and push constant ranges.
+    ///
+    /// Non-empty `push_constant_ranges` require [`Features::PUSH_CONSTANTS`](crate::Features::PUSH_CONSTANTS).
+    fn create_pipeline_layout_with_push_constants(
+        &self,
+        bind_group_layouts: &[&crate::BindGroupLayout],
+        push_constant_ranges: &[crate::PushConstantRange],
+    ) -> crate::PipelineLayout;
+
+    /// Creates an unlabeled [BindGroup](crate::BindGroup) binding `resources[i]` to
+    /// binding `i` of `layout`.
+    ///
+    /// Only works for layouts whose bindings are numbered `0..n` without gaps. Sparse
+    /// layouts need [`Device::create_bind_group`](crate::Device::create_bind_group).
+    fn create_bind_group_simple(
+        &self,
+        layout: &crate::BindGroupLayout,
+        resources: &[crate::BindingResource],
+    ) -> crate::BindGroup;
+
+    /// Creates a [ComputePipeline](crate::ComputePipeline) running `entry_point` of the
+    /// given shader, with a pipeline layout made of `bind_group_layouts`.
+    ///
+    /// Pass an empty slice for kernels without bind groups. The pipeline gets the label
+    /// of the shader.
+    fn create_compute_pipeline_simple(
+        &self,
+        shader: &crate::ShaderModuleDescriptor,
+        entry_point: &str,
+        bind_group_layouts: &[&crate::BindGroupLayout],
+    ) -> crate::ComputePipeline;
+
+    /// Creates a [ShaderModule](crate::ShaderModule) from a file read at runtime, labeled
+    /// with its path.
+    ///
+    /// Files with the `wgsl` extension are loaded as WGSL, and files with the `spv`
+    /// extension as SPIR-V binaries. With the `naga` feature, WGSL sources are validated
+    /// before the module is created, so errors are returned rather than reported to the
+    /// uncaptured error handler.
+    fn create_shader_module_from_path(
+        &self,
+        path: &std::path::Path,
+    ) -> Result<crate::ShaderModule, super::ShaderLoadError>;
 }
 
 impl DeviceExt for crate::Device {
@@ -52,9 +125,8 @@ impl DeviceExt for crate::Device {
             // 1. buffer size must be a multiple of COPY_BUFFER_ALIGNMENT.
             // 2. buffer size must be greater than 0.
             // Therefore we round the value up to the nearest multiple, and ensure it's at least COPY_BUFFER_ALIGNMENT.
-            let align_mask = crate::COPY_BUFFER_ALIGNMENT - 1;
             let padded_size =
-                ((unpadded_size + align_mask) & !align_mask).max(crate::COPY_BUFFER_ALIGNMENT);
+                super::align_to_copy_buffer(unpadded_size).max(crate::COPY_BUFFER_ALIGNMENT);
 
             let wgt_descriptor = crate::BufferDescriptor {
                 label: descriptor.label,
@@ -73,6 +145,31 @@ impl DeviceExt for crate::Device {
         }
     }
 
+    fn create_buffer_init_aligned(
+        &self,
+        descriptor: &BufferInitDescriptor<'_>,
+        alignment: crate::BufferAddress,
+    ) -> crate::Buffer {
+        let unpadded_size = descriptor.contents.len();
+        let buffer = self.create_buffer(&crate::BufferDescriptor {
+            label: descriptor.label,
+            size: aligned_buffer_size(unpadded_size as crate::BufferAddress, alignment),
+            usage: descriptor.usage,
+            mapped_at_creation: true,
+        });
+
+        {
+            let mut mapped = buffer.slice(..).get_mapped_range_mut();
+            mapped[..unpadded_size].copy_from_slice(descriptor.contents);
+            for byte in mapped[unpadded_size..].iter_mut() {
+                *byte = 0;
+            }
+        }
+        buffer.unmap();
+
+        buffer
+    }
+
     fn create_texture_with_data(
         &self,
         queue: &crate::Queue,
@@ -117,6 +214,14 @@ impl DeviceExt for crate::Device {
                 let data_size = bytes_per_row * height_blocks * mip_extent.depth_or_array_layers;
 
                 let end_offset = binary_offset + data_size as usize;
+                assert!(
+                    end_offset <= data.len(),
+                    "data is too small for layer {} mip {}: expected at least {} bytes, got {}",
+                    layer,
+                    mip,
+                    end_offset,
+                    data.len()
+                );
 
                 queue.write_texture(
                     crate::ImageCopyTexture {
@@ -147,4 +252,158 @@ impl DeviceExt for crate::Device {
 
         texture
     }
+
+    fn create_pipeline_layout_simple(
+        &self,
+        bind_group_layouts: &[&crate::BindGroupLayout],
+    ) -> crate::PipelineLayout {
+        self.create_pipeline_layout_with_push_constants(bind_group_layouts, &[])
+    }
+
+    fn create_pipeline_layout_with_push_constants(
+        &self,
+        bind_group_layouts: &[&crate::BindGroupLayout],
+        push_constant_ranges: &[crate::PushConstantRange],
+    ) -> crate::PipelineLayout {
+        self.create_pipeline_layout(&crate::PipelineLayoutDescriptor {
+            label: None,
+            bind_group_layouts,
+            push_constant_ranges,
+        })
+    }
+
+    fn create_bind_group_simple(
+        &self,
+        layout: &crate::BindGroupLayout,
+        resources: &[crate::BindingResource],
+    ) -> crate::BindGroup {
+        self.create_bind_group(&crate::BindGroupDescriptor {
+            label: None,
+            layout,
+            entries: &sequential_entries(resources),
+        })
+    }
+
+    fn create_compute_pipeline_simple(
+        &self,
+        shader: &crate::ShaderModuleDescriptor,
+        entry_point: &str,
+        bind_group_layouts: &[&crate::BindGroupLayout],
+    ) -> crate::ComputePipeline {
+        let module = self.create_shader_module(shader);
+        let layout = self.create_pipeline_layout_simple(bind_group_layouts);
+        self.create_compute_pipeline(&crate::ComputePipelineDescriptor {
+            label: shader.label,
+            layout: Some(&layout),
+            module: &module,
+            entry_point,
+        })
+    }
+
+    fn create_shader_module_from_path(
+        &self,
+        path: &std::path::Path,
+    ) -> Result<crate::ShaderModule, super::ShaderLoadError> {
+        let source = super::shader::load_shader_source(path)?;
+        let flags = match source {
+            crate::ShaderSource::Wgsl(_) => crate::ShaderFlags::all(),
+            crate::ShaderSource::SpirV(_) => crate::ShaderFlags::VALIDATION,
+            #[cfg(feature = "naga")]
+            crate::ShaderSource::Naga(_) => crate::ShaderFlags::all(),
+        };
+        Ok(self.create_shader_module(&crate::ShaderModuleDescriptor {
+            label: path.to_str(),
+            source,
+            flags,
+        }))
+    }
+}
+
+/// Size of a buffer created by [`DeviceExt::create_buffer_init_aligned`].
+fn aligned_buffer_size(
+    unpadded_size: crate::BufferAddress,
+    alignment: crate::BufferAddress,
+) -> crate::BufferAddress {
+    let alignment = alignment.max(crate::COPY_BUFFER_ALIGNMENT);
+    super::align_to(unpadded_size, alignment).max(alignment)
+}
+
+/// Entries of a [`DeviceExt::create_bind_group_simple`] bind group.
+fn sequential_entries<'a>(
+    resources: &[crate::BindingResource<'a>],
+) -> Vec<crate::BindGroupEntry<'a>> {
+    resources
+        .iter()
+        .enumerate()
+        .map(|(binding, resource)| crate::BindGroupEntry {
+            binding: binding as u32,
+            resource: resource.clone(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{aligned_buffer_size, sequential_entries};
+
+    #[test]
+    fn aligned_buffer_size_rounds_up() {
+        assert_eq!(aligned_buffer_size(3, 256), 256);
+        assert_eq!(aligned_buffer_size(256, 256), 256);
+        assert_eq!(aligned_buffer_size(300, 256), 512);
+    }
+
+    #[test]
+    fn aligned_buffer_size_minimums() {
+        // Empty contents still get a non-zero buffer
+        assert_eq!(aligned_buffer_size(0, 256), 256);
+        // Sizes stay a multiple of COPY_BUFFER_ALIGNMENT
+        assert_eq!(aligned_buffer_size(3, 1), 4);
+        assert_eq!(aligned_buffer_size(5, 2), 8);
+    }
+
+    #[test]
+    fn sequential_entries_bindings() {
+        use crate::BindingResource;
+
+        let entries = sequential_entries(&[
+            BindingResource::BufferArray(&[]),
+            BindingResource::TextureViewArray(&[]),
+        ]);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].binding, 0);
+        assert!(matches!(
+            entries[0].resource,
+            BindingResource::BufferArray(_)
+        ));
+        assert_eq!(entries[1].binding, 1);
+        assert!(matches!(
+            entries[1].resource,
+            BindingResource::TextureViewArray(_)
+        ));
+    }
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn create_texture_with_data_reads_back() {
+        use super::DeviceExt;
+        use crate::util::{read_test_texture, test_device, TextureDescriptorExt};
+
+        let (device, queue) = match test_device() {
+            Some(device) => device,
+            None => return,
+        };
+        let desc = crate::TextureDescriptor::d2(
+            4,
+            4,
+            crate::TextureFormat::Rgba8Unorm,
+            crate::TextureUsage::COPY_DST | crate::TextureUsage::COPY_SRC,
+        )
+        .with_mips(2);
+        // Tightly packed rows of 16 and then 8 bytes, one mip level after the other
+        let data: Vec<u8> = (0..(4 * 4 + 2 * 2) * 4).map(|byte| byte as u8).collect();
+        let texture = device.create_texture_with_data(&queue, &desc, &data);
+
+        assert_eq!(read_test_texture(&device, &queue, &texture, 0), &data[..64]);
+        assert_eq!(read_test_texture(&device, &queue, &texture, 1), &data[64..]);
+    }
 }
@@ -0,0 +1,91 @@
+use wgt::{DownlevelFlags, DownlevelProperties};
+
+/// Names of the individual [`DownlevelFlags`], as spelled in code, and what they provide.
+const DOWNLEVEL_FLAGS: &[(DownlevelFlags, &str, &str)] = &[
+    (
+        DownlevelFlags::COMPUTE_SHADERS,
+        "COMPUTE_SHADERS",
+        "compute shaders",
+    ),
+    (
+        DownlevelFlags::STORAGE_IMAGES,
+        "STORAGE_IMAGES",
+        "storage textures",
+    ),
+    (
+        DownlevelFlags::READ_ONLY_DEPTH_STENCIL,
+        "READ_ONLY_DEPTH_STENCIL",
+        "read-only depth stencil attachments",
+    ),
+    (
+        DownlevelFlags::DEVICE_LOCAL_IMAGE_COPIES,
+        "DEVICE_LOCAL_IMAGE_COPIES",
+        "copies between textures in device local memory",
+    ),
+    (
+        DownlevelFlags::NON_POWER_OF_TWO_MIPMAPPED_TEXTURES,
+        "NON_POWER_OF_TWO_MIPMAPPED_TEXTURES",
+        "mipmapped textures with a size that isn't a power of two",
+    ),
+    (
+        DownlevelFlags::ANISOTROPIC_FILTERING,
+        "ANISOTROPIC_FILTERING",
+        "anisotropic filtering",
+    ),
+];
+
+/// Names of the [`DownlevelFlags`] flags.
+pub trait DownlevelFlagsExt {
+    /// Names of the flags in this set, in declaration order.
+    fn names(&self) -> Vec<&'static str>;
+}
+
+impl DownlevelFlagsExt for DownlevelFlags {
+    fn names(&self) -> Vec<&'static str> {
+        DOWNLEVEL_FLAGS
+            .iter()
+            .filter(|&&(flag, _, _)| self.contains(flag))
+            .map(|&(_, name, _)| name)
+            .collect()
+    }
+}
+
+/// Describes the `required` capabilities missing from `caps`, one entry per flag.
+///
+/// The entries are plain descriptions such as `"compute shaders"`, meant to tell users
+/// what their hardware or browser lacks. Use [`DownlevelFlagsExt::names`] on the difference
+/// for the flag names instead.
+pub fn downlevel_report(caps: &DownlevelProperties, required: DownlevelFlags) -> Vec<&'static str> {
+    let missing = required - caps.flags;
+    DOWNLEVEL_FLAGS
+        .iter()
+        .filter(|&&(flag, _, _)| missing.contains(flag))
+        .map(|&(_, _, description)| description)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{downlevel_report, DownlevelFlagsExt};
+    use wgt::{DownlevelFlags, DownlevelProperties};
+
+    #[test]
+    fn downlevel_flag_names() {
+        assert!(DownlevelFlags::empty().names().is_empty());
+        assert_eq!(
+            (DownlevelFlags::ANISOTROPIC_FILTERING | DownlevelFlags::COMPUTE_SHADERS).names(),
+            ["COMPUTE_SHADERS", "ANISOTROPIC_FILTERING"]
+        );
+    }
+
+    #[test]
+    fn downlevel_report_names_missing_compute() {
+        let caps = DownlevelProperties {
+            flags: DownlevelFlags::STORAGE_IMAGES | DownlevelFlags::ANISOTROPIC_FILTERING,
+            ..DownlevelProperties::default()
+        };
+        let required = DownlevelFlags::COMPUTE_SHADERS | DownlevelFlags::STORAGE_IMAGES;
+        assert_eq!(downlevel_report(&caps, required), ["compute shaders"]);
+        assert!(downlevel_report(&caps, DownlevelFlags::STORAGE_IMAGES).is_empty());
+    }
+}
@@ -0,0 +1,193 @@
+use std::{error, fmt::Display};
+
+use wgt::Features;
+
+/// Names of the individual [`Features`] flags, as spelled in code.
+const FEATURE_NAMES: &[(&str, Features)] = &[
+    ("DEPTH_CLAMPING", Features::DEPTH_CLAMPING),
+    ("TEXTURE_COMPRESSION_BC", Features::TEXTURE_COMPRESSION_BC),
+    ("TIMESTAMP_QUERY", Features::TIMESTAMP_QUERY),
+    (
+        "PIPELINE_STATISTICS_QUERY",
+        Features::PIPELINE_STATISTICS_QUERY,
+    ),
+    (
+        "MAPPABLE_PRIMARY_BUFFERS",
+        Features::MAPPABLE_PRIMARY_BUFFERS,
+    ),
+    (
+        "SAMPLED_TEXTURE_BINDING_ARRAY",
+        Features::SAMPLED_TEXTURE_BINDING_ARRAY,
+    ),
+    (
+        "SAMPLED_TEXTURE_ARRAY_DYNAMIC_INDEXING",
+        Features::SAMPLED_TEXTURE_ARRAY_DYNAMIC_INDEXING,
+    ),
+    (
+        "SAMPLED_TEXTURE_ARRAY_NON_UNIFORM_INDEXING",
+        Features::SAMPLED_TEXTURE_ARRAY_NON_UNIFORM_INDEXING,
+    ),
+    ("UNSIZED_BINDING_ARRAY", Features::UNSIZED_BINDING_ARRAY),
+    ("MULTI_DRAW_INDIRECT", Features::MULTI_DRAW_INDIRECT),
+    (
+        "MULTI_DRAW_INDIRECT_COUNT",
+        Features::MULTI_DRAW_INDIRECT_COUNT,
+    ),
+    ("PUSH_CONSTANTS", Features::PUSH_CONSTANTS),
+    (
+        "ADDRESS_MODE_CLAMP_TO_BORDER",
+        Features::ADDRESS_MODE_CLAMP_TO_BORDER,
+    ),
+    ("NON_FILL_POLYGON_MODE", Features::NON_FILL_POLYGON_MODE),
+    (
+        "TEXTURE_COMPRESSION_ETC2",
+        Features::TEXTURE_COMPRESSION_ETC2,
+    ),
+    (
+        "TEXTURE_COMPRESSION_ASTC_LDR",
+        Features::TEXTURE_COMPRESSION_ASTC_LDR,
+    ),
+    (
+        "TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES",
+        Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES,
+    ),
+    ("SHADER_FLOAT64", Features::SHADER_FLOAT64),
+    ("VERTEX_ATTRIBUTE_64BIT", Features::VERTEX_ATTRIBUTE_64BIT),
+    (
+        "CONSERVATIVE_RASTERIZATION",
+        Features::CONSERVATIVE_RASTERIZATION,
+    ),
+    ("BUFFER_BINDING_ARRAY", Features::BUFFER_BINDING_ARRAY),
+    (
+        "UNIFORM_BUFFER_ARRAY_DYNAMIC_INDEXING",
+        Features::UNIFORM_BUFFER_ARRAY_DYNAMIC_INDEXING,
+    ),
+    (
+        "UNIFORM_BUFFER_ARRAY_NON_UNIFORM_INDEXING",
+        Features::UNIFORM_BUFFER_ARRAY_NON_UNIFORM_INDEXING,
+    ),
+    (
+        "STORAGE_BUFFER_ARRAY_DYNAMIC_INDEXING",
+        Features::STORAGE_BUFFER_ARRAY_DYNAMIC_INDEXING,
+    ),
+    (
+        "STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING",
+        Features::STORAGE_BUFFER_ARRAY_NON_UNIFORM_INDEXING,
+    ),
+];
+
+/// Error returned by [`FeaturesExt::from_names`], holding the name that was not recognized.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnknownFeatureName(pub String);
+
+impl Display for UnknownFeatureName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unknown feature name {:?}", self.0)
+    }
+}
+
+impl error::Error for UnknownFeatureName {}
+
+/// Error returned by [`negotiate_device_descriptor`](super::negotiate_device_descriptor),
+/// holding the required features the adapter lacks.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MissingFeatures(pub Features);
+
+impl Display for MissingFeatures {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Adapter is missing the required features {}",
+            self.0.names().join(", ")
+        )
+    }
+}
+
+impl error::Error for MissingFeatures {}
+
+/// Conversions between [`Features`] and the names of its flags, for features requested
+/// through configuration files.
+pub trait FeaturesExt: Sized {
+    /// Parses flag names such as `"TIMESTAMP_QUERY"` into a set of features.
+    ///
+    /// Names are matched ignoring ASCII case.
+    fn from_names(names: &[&str]) -> Result<Self, UnknownFeatureName>;
+
+    /// Names of the flags in this set, in declaration order.
+    fn names(&self) -> Vec<&'static str>;
+}
+
+impl FeaturesExt for Features {
+    fn from_names(names: &[&str]) -> Result<Self, UnknownFeatureName> {
+        names.iter().try_fold(Features::empty(), |features, &name| {
+            FEATURE_NAMES
+                .iter()
+                .find(|&&(known, _)| known.eq_ignore_ascii_case(name))
+                .map(|&(_, feature)| features | feature)
+                .ok_or_else(|| UnknownFeatureName(name.to_owned()))
+        })
+    }
+
+    fn names(&self) -> Vec<&'static str> {
+        FEATURE_NAMES
+            .iter()
+            .filter(|&&(_, feature)| self.contains(feature))
+            .map(|&(name, _)| name)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FeaturesExt, UnknownFeatureName};
+    use wgt::Features;
+
+    #[test]
+    fn feature_names_cover_all_flags() {
+        // `Features::all` also holds the bits reserved by ALL_WEBGPU and ALL_NATIVE, so the
+        // declared flags are taken from its debug output instead
+        let debug = format!("{:?}", Features::all());
+        let declared: Vec<_> = debug
+            .split(" | ")
+            .filter(|name| !name.starts_with("ALL_"))
+            .collect();
+        let features = Features::from_names(&declared).unwrap();
+        assert_eq!(features.names(), declared);
+        assert_eq!(Features::from_names(&features.names()), Ok(features));
+    }
+
+    #[test]
+    fn feature_names_round_trip() {
+        let combinations = [
+            Features::empty(),
+            Features::TIMESTAMP_QUERY | Features::PUSH_CONSTANTS,
+            Features::DEPTH_CLAMPING
+                | Features::TEXTURE_COMPRESSION_BC
+                | Features::MULTI_DRAW_INDIRECT_COUNT,
+        ];
+        for &features in combinations.iter() {
+            let names = features.names();
+            assert_eq!(names.len(), features.bits().count_ones() as usize);
+            assert_eq!(Features::from_names(&names), Ok(features));
+        }
+    }
+
+    #[test]
+    fn feature_names_parse() {
+        assert_eq!(
+            Features::from_names(&["TIMESTAMP_QUERY", "push_constants"]),
+            Ok(Features::TIMESTAMP_QUERY | Features::PUSH_CONSTANTS)
+        );
+        assert_eq!(
+            (Features::PUSH_CONSTANTS | Features::TIMESTAMP_QUERY).names(),
+            ["TIMESTAMP_QUERY", "PUSH_CONSTANTS"]
+        );
+    }
+
+    #[test]
+    fn feature_names_unknown() {
+        let err = Features::from_names(&["PUSH_CONSTANTS", "WARP_DRIVE"]).unwrap_err();
+        assert_eq!(err, UnknownFeatureName("WARP_DRIVE".to_owned()));
+        assert!(err.to_string().contains("WARP_DRIVE"));
+    }
+}
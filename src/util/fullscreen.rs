@@ -0,0 +1,120 @@
+use std::borrow::Cow;
+
+/// Describes a WGSL module with a `vs_main` vertex entry point emitting a single
+/// triangle that covers the whole render target, without any vertex buffer.
+///
+/// The vertex shader outputs texture coordinates at `location(0)`, going from `(0, 0)`
+/// at the top left corner of the target to `(1, 1)` at the bottom right corner.
+/// A fragment shader paired with it can take them as input:
+///
+/// ```ignore
+/// [[stage(fragment)]]
+/// fn fs_main([[location(0)]] tex_coords: vec2<f32>) -> [[location(0)]] vec4<f32> {
+///     return textureSample(r_color, r_sampler, tex_coords);
+/// }
+/// ```
+///
+/// Draw it with `render_pass.draw(0..3, 0..1)`, or see [`create_fullscreen_pipeline`].
+pub fn fullscreen_triangle_shader() -> crate::ShaderModuleDescriptor<'static> {
+    crate::ShaderModuleDescriptor {
+        label: Some("fullscreen triangle"),
+        source: crate::ShaderSource::Wgsl(Cow::Borrowed(include_str!("fullscreen.wgsl"))),
+        flags: crate::ShaderFlags::all(),
+    }
+}
+
+/// Creates a render pipeline drawing the [fullscreen triangle](fullscreen_triangle_shader)
+/// with the given fragment shader entry point, into a single `target_format` color target.
+///
+/// The pipeline layout is derived from the shaders. Draw it with
+/// `render_pass.draw(0..3, 0..1)`.
+pub fn create_fullscreen_pipeline(
+    device: &crate::Device,
+    fragment_shader: &crate::ShaderModule,
+    fragment_entry_point: &str,
+    target_format: crate::TextureFormat,
+) -> crate::RenderPipeline {
+    let vertex_shader = device.create_shader_module(&fullscreen_triangle_shader());
+    device.create_render_pipeline(&crate::RenderPipelineDescriptor {
+        label: Some("fullscreen"),
+        layout: None,
+        vertex: crate::VertexState {
+            module: &vertex_shader,
+            entry_point: "vs_main",
+            buffers: &[],
+        },
+        fragment: Some(crate::FragmentState {
+            module: fragment_shader,
+            entry_point: fragment_entry_point,
+            targets: &[target_format.into()],
+        }),
+        primitive: crate::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: crate::MultisampleState::default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(feature = "naga")]
+    fn fullscreen_triangle_shader_is_valid() {
+        crate::util::validate_wgsl(include_str!("fullscreen.wgsl")).unwrap();
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn fullscreen_triangle_covers_target() {
+        use super::create_fullscreen_pipeline;
+        use crate::util::{read_test_texture, test_device, TextureDescriptorExt};
+        use std::borrow::Cow;
+
+        let (device, queue) = match test_device() {
+            Some(device) => device,
+            None => return,
+        };
+        let format = crate::TextureFormat::Rgba8Unorm;
+        let target = device.create_texture(&crate::TextureDescriptor::d2(
+            4,
+            4,
+            format,
+            crate::TextureUsage::RENDER_ATTACHMENT | crate::TextureUsage::COPY_SRC,
+        ));
+        let fragment_shader = device.create_shader_module(&crate::ShaderModuleDescriptor {
+            label: None,
+            source: crate::ShaderSource::Wgsl(Cow::Borrowed(
+                "[[stage(fragment)]]
+                fn fs_main([[location(0)]] tex_coords: vec2<f32>) -> [[location(0)]] vec4<f32> {
+                    return vec4<f32>(1.0, 0.0, 1.0, 1.0);
+                }",
+            )),
+            flags: crate::ShaderFlags::all(),
+        });
+        let pipeline = create_fullscreen_pipeline(&device, &fragment_shader, "fs_main", format);
+
+        let view = target.default_view();
+        let mut encoder =
+            device.create_command_encoder(&crate::CommandEncoderDescriptor { label: None });
+        {
+            let mut pass = encoder.begin_render_pass(&crate::RenderPassDescriptor {
+                label: None,
+                color_attachments: &[crate::RenderPassColorAttachment {
+                    view: &view,
+                    resolve_target: None,
+                    ops: crate::Operations {
+                        load: crate::LoadOp::Clear(crate::Color::TRANSPARENT),
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: None,
+            });
+            pass.set_pipeline(&pipeline);
+            pass.draw(0..3, 0..1);
+        }
+        queue.submit(Some(encoder.finish()));
+
+        let pixels = read_test_texture(&device, &queue, &target, 0);
+        assert_eq!(pixels.len(), 4 * 4 * 4);
+        assert!(pixels.chunks(4).all(|pixel| pixel == [0xff, 0, 0xff, 0xff]));
+    }
+}
@@ -0,0 +1,15 @@
+struct VertexOutput {
+    [[location(0)]] tex_coords: vec2<f32>;
+    [[builtin(position)]] position: vec4<f32>;
+};
+
+// Emits the triangle (-1, -1), (3, -1), (-1, 3), which covers the whole clip space.
+[[stage(vertex)]]
+fn vs_main([[builtin(vertex_index)]] vertex_index: u32) -> VertexOutput {
+    let x = f32(i32(vertex_index & 1u) * 4 - 1);
+    let y = f32(i32(vertex_index >> 1u) * 4 - 1);
+    var out: VertexOutput;
+    out.tex_coords = vec2<f32>((x + 1.0) * 0.5, (1.0 - y) * 0.5);
+    out.position = vec4<f32>(x, y, 0.0, 1.0);
+    return out;
+}
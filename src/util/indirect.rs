@@ -0,0 +1,142 @@
+use std::{
+    mem::{size_of, size_of_val},
+    slice,
+};
+
+/// Arguments of [`RenderPass::draw_indirect`](crate::RenderPass::draw_indirect),
+/// laid out the way they are expected in the indirect buffer.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct DrawIndirectArgs {
+    /// The number of vertices to draw.
+    pub vertex_count: u32,
+    /// The number of instances to draw.
+    pub instance_count: u32,
+    /// The Index of the first vertex to draw.
+    pub first_vertex: u32,
+    /// The instance ID of the first instance to draw.
+    pub first_instance: u32,
+}
+
+impl DrawIndirectArgs {
+    /// Returns the bytes to write into the indirect buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        // Safe because the struct is `repr(C)` and only holds `u32`s, so it has no padding.
+        unsafe { slice::from_raw_parts(self as *const Self as *const u8, size_of::<Self>()) }
+    }
+
+    /// Returns the bytes of several tightly packed draws, to write into the indirect buffer of
+    /// [`RenderPass::multi_draw_indirect`](crate::RenderPass::multi_draw_indirect) and the like.
+    pub fn slice_as_bytes(args: &[Self]) -> &[u8] {
+        // Safe because the struct is `repr(C)` and only holds `u32`s, so it has no padding.
+        unsafe { slice::from_raw_parts(args.as_ptr() as *const u8, size_of_val(args)) }
+    }
+}
+
+/// Arguments of [`RenderPass::draw_indexed_indirect`](crate::RenderPass::draw_indexed_indirect),
+/// laid out the way they are expected in the indirect buffer.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct DrawIndexedIndirectArgs {
+    /// The number of indices to draw.
+    pub index_count: u32,
+    /// The number of instances to draw.
+    pub instance_count: u32,
+    /// The first index within the index buffer.
+    pub first_index: u32,
+    /// The value added to the vertex index before indexing into the vertex buffer.
+    pub base_vertex: i32,
+    /// The instance ID of the first instance to draw.
+    pub first_instance: u32,
+}
+
+impl DrawIndexedIndirectArgs {
+    /// Returns the bytes to write into the indirect buffer.
+    pub fn as_bytes(&self) -> &[u8] {
+        // Safe because the struct is `repr(C)` and only holds 4 byte integers, so it has no padding.
+        unsafe { slice::from_raw_parts(self as *const Self as *const u8, size_of::<Self>()) }
+    }
+
+    /// Returns the bytes of several tightly packed draws, to write into the indirect buffer of
+    /// [`RenderPass::multi_draw_indirect`](crate::RenderPass::multi_draw_indirect) and the like.
+    pub fn slice_as_bytes(args: &[Self]) -> &[u8] {
+        // Safe because the struct is `repr(C)` and only holds 4 byte integers, so it has no padding.
+        unsafe { slice::from_raw_parts(args.as_ptr() as *const u8, size_of_val(args)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DrawIndexedIndirectArgs, DrawIndirectArgs};
+    use std::mem::size_of;
+
+    fn offset_of<T, F>(base: &T, field: &F) -> usize {
+        field as *const F as usize - base as *const T as usize
+    }
+
+    #[test]
+    fn draw_indirect_args_layout() {
+        assert_eq!(size_of::<DrawIndirectArgs>(), 16);
+
+        let args = DrawIndirectArgs::default();
+        assert_eq!(offset_of(&args, &args.vertex_count), 0);
+        assert_eq!(offset_of(&args, &args.instance_count), 4);
+        assert_eq!(offset_of(&args, &args.first_vertex), 8);
+        assert_eq!(offset_of(&args, &args.first_instance), 12);
+    }
+
+    #[test]
+    fn draw_indexed_indirect_args_layout() {
+        assert_eq!(size_of::<DrawIndexedIndirectArgs>(), 20);
+
+        let args = DrawIndexedIndirectArgs::default();
+        assert_eq!(offset_of(&args, &args.index_count), 0);
+        assert_eq!(offset_of(&args, &args.instance_count), 4);
+        assert_eq!(offset_of(&args, &args.first_index), 8);
+        assert_eq!(offset_of(&args, &args.base_vertex), 12);
+        assert_eq!(offset_of(&args, &args.first_instance), 16);
+    }
+
+    #[test]
+    fn indirect_args_as_bytes() {
+        let args = DrawIndexedIndirectArgs {
+            index_count: 1,
+            instance_count: 2,
+            first_index: 3,
+            base_vertex: -1,
+            first_instance: 5,
+        };
+        let bytes = args.as_bytes();
+        assert_eq!(bytes.len(), 20);
+        assert_eq!(&bytes[4..8], &2u32.to_ne_bytes());
+        assert_eq!(&bytes[12..16], &(-1i32).to_ne_bytes());
+    }
+
+    #[test]
+    fn multi_draw_args_as_bytes() {
+        let draws = [
+            DrawIndirectArgs {
+                vertex_count: 3,
+                instance_count: 1,
+                first_vertex: 0,
+                first_instance: 0,
+            },
+            DrawIndirectArgs {
+                vertex_count: 6,
+                instance_count: 2,
+                first_vertex: 3,
+                first_instance: 1,
+            },
+        ];
+        let bytes = DrawIndirectArgs::slice_as_bytes(&draws);
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(&bytes[..16], draws[0].as_bytes());
+        assert_eq!(&bytes[16..], draws[1].as_bytes());
+
+        let indexed = [DrawIndexedIndirectArgs::default(); 3];
+        assert_eq!(DrawIndexedIndirectArgs::slice_as_bytes(&indexed).len(), 60);
+        assert!(DrawIndirectArgs::slice_as_bytes(&[]).is_empty());
+    }
+}
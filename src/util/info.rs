@@ -0,0 +1,98 @@
+use wgt::{AdapterInfo, Backend, DeviceType};
+
+/// Serializable mirror of [`AdapterInfo`], for bug reports and telemetry.
+///
+/// Vendor and device IDs are serialized as plain integers, while the device type
+/// and backend are serialized as lowercase string tags:
+///
+/// ```ignore
+/// let info = wgpu::util::SerializableAdapterInfo::from(adapter.get_info());
+/// println!("{}", serde_json::to_string(&info).unwrap());
+/// ```
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct SerializableAdapterInfo {
+    /// Adapter name
+    pub name: String,
+    /// Vendor PCI id of the adapter
+    pub vendor: usize,
+    /// PCI id of the adapter
+    pub device: usize,
+    /// Type of device
+    #[serde(with = "DeviceTypeDef")]
+    pub device_type: DeviceType,
+    /// Backend used for device
+    #[serde(with = "BackendDef")]
+    pub backend: Backend,
+}
+
+impl From<AdapterInfo> for SerializableAdapterInfo {
+    fn from(info: AdapterInfo) -> Self {
+        Self {
+            name: info.name,
+            vendor: info.vendor,
+            device: info.device,
+            device_type: info.device_type,
+            backend: info.backend,
+        }
+    }
+}
+
+impl From<SerializableAdapterInfo> for AdapterInfo {
+    fn from(info: SerializableAdapterInfo) -> Self {
+        Self {
+            name: info.name,
+            vendor: info.vendor,
+            device: info.device,
+            device_type: info.device_type,
+            backend: info.backend,
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "DeviceType", rename_all = "lowercase")]
+enum DeviceTypeDef {
+    Other,
+    IntegratedGpu,
+    DiscreteGpu,
+    VirtualGpu,
+    Cpu,
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(remote = "Backend", rename_all = "lowercase")]
+enum BackendDef {
+    Empty,
+    Vulkan,
+    Metal,
+    Dx12,
+    Dx11,
+    Gl,
+    BrowserWebGpu,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SerializableAdapterInfo;
+    use wgt::{AdapterInfo, Backend, DeviceType};
+
+    #[test]
+    fn adapter_info_round_trip() {
+        let info = AdapterInfo {
+            name: "Test Adapter".to_string(),
+            vendor: 0x10de,
+            device: 0x1b80,
+            device_type: DeviceType::DiscreteGpu,
+            backend: Backend::Vulkan,
+        };
+
+        let json = serde_json::to_string(&SerializableAdapterInfo::from(info.clone())).unwrap();
+        assert_eq!(
+            json,
+            r#"{"name":"Test Adapter","vendor":4318,"device":7040,"device_type":"discretegpu","backend":"vulkan"}"#
+        );
+
+        let parsed: SerializableAdapterInfo = serde_json::from_str(&json).unwrap();
+        assert_eq!(AdapterInfo::from(parsed), info);
+    }
+}
@@ -0,0 +1,150 @@
+use wgt::Limits;
+
+/// Invokes `$macro` with the names of all the [`Limits`] fields.
+macro_rules! for_each_limit {
+    ($macro:ident) => {
+        $macro!(
+            max_texture_dimension_1d,
+            max_texture_dimension_2d,
+            max_texture_dimension_3d,
+            max_texture_array_layers,
+            max_bind_groups,
+            max_dynamic_uniform_buffers_per_pipeline_layout,
+            max_dynamic_storage_buffers_per_pipeline_layout,
+            max_sampled_textures_per_shader_stage,
+            max_samplers_per_shader_stage,
+            max_storage_buffers_per_shader_stage,
+            max_storage_textures_per_shader_stage,
+            max_uniform_buffers_per_shader_stage,
+            max_uniform_buffer_binding_size,
+            max_storage_buffer_binding_size,
+            max_vertex_buffers,
+            max_vertex_attributes,
+            max_vertex_buffer_array_stride,
+            max_push_constant_size,
+        )
+    };
+}
+
+/// Comparison and clamping of [`Limits`], to check an adapter against what an application
+/// needs before requesting a device.
+pub trait LimitsExt {
+    /// Whether every limit of `self` is at least as high as the one in `required`.
+    fn check_limits(&self, required: &Limits) -> bool;
+
+    /// Like [`check_limits`](LimitsExt::check_limits), calling
+    /// `fail_fn(name, actual, required)` for each limit of `self` that is too low.
+    fn check_limits_with_fail_fn(
+        &self,
+        required: &Limits,
+        fail_fn: impl FnMut(&'static str, u32, u32),
+    ) -> bool;
+
+    /// Lowers each limit of `self` above the one in `supported` down to it.
+    fn clamp_to(&self, supported: &Limits) -> Limits;
+}
+
+impl LimitsExt for Limits {
+    fn check_limits(&self, required: &Limits) -> bool {
+        self.check_limits_with_fail_fn(required, |_, _, _| {})
+    }
+
+    fn check_limits_with_fail_fn(
+        &self,
+        required: &Limits,
+        mut fail_fn: impl FnMut(&'static str, u32, u32),
+    ) -> bool {
+        let mut within = true;
+        macro_rules! check {
+            ($($name:ident),* $(,)?) => {
+                $(
+                    if self.$name < required.$name {
+                        within = false;
+                        fail_fn(stringify!($name), self.$name, required.$name);
+                    }
+                )*
+            };
+        }
+        for_each_limit!(check);
+        within
+    }
+
+    fn clamp_to(&self, supported: &Limits) -> Limits {
+        let mut clamped = self.clone();
+        macro_rules! clamp {
+            ($($name:ident),* $(,)?) => {
+                $(
+                    clamped.$name = clamped.$name.min(supported.$name);
+                )*
+            };
+        }
+        for_each_limit!(clamp);
+        clamped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LimitsExt;
+    use wgt::Limits;
+
+    #[test]
+    fn limits_within() {
+        let limits = Limits::default();
+        assert!(limits.check_limits(&limits));
+
+        let lower = Limits {
+            max_bind_groups: 2,
+            ..Limits::default()
+        };
+        assert!(limits.check_limits(&lower));
+    }
+
+    #[test]
+    fn limits_failing_field_reported() {
+        let adapter = Limits {
+            max_uniform_buffer_binding_size: 1 << 10,
+            ..Limits::default()
+        };
+        let required = Limits {
+            max_push_constant_size: 0,
+            ..Limits::default()
+        };
+
+        let mut failures = Vec::new();
+        let within = adapter.check_limits_with_fail_fn(&required, |name, actual, required| {
+            failures.push((name, actual, required))
+        });
+        assert!(!within);
+        assert!(!adapter.check_limits(&required));
+        assert_eq!(
+            failures,
+            [(
+                "max_uniform_buffer_binding_size",
+                1 << 10,
+                Limits::default().max_uniform_buffer_binding_size
+            )]
+        );
+    }
+
+    #[test]
+    fn limits_clamped() {
+        let supported = Limits {
+            max_bind_groups: 2,
+            ..Limits::default()
+        };
+        let requested = Limits {
+            max_push_constant_size: 128,
+            ..Limits::default()
+        };
+        assert_eq!(
+            requested.clamp_to(&supported),
+            Limits {
+                max_bind_groups: 2,
+                max_push_constant_size: 0,
+                ..Limits::default()
+            }
+        );
+        assert_eq!(supported.clamp_to(&Limits::default()), supported);
+    }
+}
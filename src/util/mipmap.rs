@@ -0,0 +1,177 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use super::create_fullscreen_pipeline;
+
+/// Generates mip chains by rendering each mip level from the previous one with linear filtering.
+///
+/// Blit pipelines are created on first use for each texture format and reused afterwards,
+/// so keep the generator around when generating mipmaps for many textures.
+#[derive(Debug)]
+pub struct MipmapGenerator {
+    shader: crate::ShaderModule,
+    sampler: crate::Sampler,
+    pipelines: HashMap<crate::TextureFormat, crate::RenderPipeline>,
+}
+
+impl MipmapGenerator {
+    /// Creates a new mipmap generator.
+    pub fn new(device: &crate::Device) -> Self {
+        let shader = device.create_shader_module(&crate::ShaderModuleDescriptor {
+            label: Some("mipmap blit"),
+            source: crate::ShaderSource::Wgsl(Cow::Borrowed(include_str!("blit.wgsl"))),
+            flags: crate::ShaderFlags::all(),
+        });
+        let sampler = device.create_sampler(&crate::SamplerDescriptor {
+            label: Some("mipmap blit"),
+            mipmap_filter: crate::FilterMode::Nearest,
+            ..crate::SamplerDescriptor::linear_clamp()
+        });
+        Self {
+            shader,
+            sampler,
+            pipelines: HashMap::new(),
+        }
+    }
+
+    /// Encodes render passes filling mip levels `1..mip_count` of the first layer of
+    /// `texture`, each from the level before it.
+    ///
+    /// `format` has to be the format of the texture, which must be a filterable color
+    /// format. The texture needs both the [`RENDER_ATTACHMENT`](crate::TextureUsage::RENDER_ATTACHMENT)
+    /// and [`SAMPLED`](crate::TextureUsage::SAMPLED) usages. Each level is `max(1, size >> level)`
+    /// texels wide and high, so non-power-of-two sizes are supported.
+    pub fn generate(
+        &mut self,
+        device: &crate::Device,
+        encoder: &mut crate::CommandEncoder,
+        texture: &crate::Texture,
+        format: crate::TextureFormat,
+        mip_count: u32,
+    ) {
+        let shader = &self.shader;
+        let pipeline = self
+            .pipelines
+            .entry(format)
+            .or_insert_with(|| create_fullscreen_pipeline(device, shader, "fs_main", format));
+        let bind_group_layout = pipeline.get_bind_group_layout(0);
+
+        let views = (0..mip_count)
+            .map(|mip| {
+                texture.create_view(&crate::TextureViewDescriptor {
+                    label: Some("mip"),
+                    ..crate::TextureViewDescriptor::mip_layer(mip, 0)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for target_mip in 1..views.len() {
+            let bind_group = device.create_bind_group(&crate::BindGroupDescriptor {
+                label: None,
+                layout: &bind_group_layout,
+                entries: &[
+                    crate::BindGroupEntry {
+                        binding: 0,
+                        resource: crate::BindingResource::TextureView(&views[target_mip - 1]),
+                    },
+                    crate::BindGroupEntry {
+                        binding: 1,
+                        resource: crate::BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            });
+
+            let mut rpass = encoder.begin_render_pass(&crate::RenderPassDescriptor {
+                label: Some("mipmap blit"),
+                color_attachments: &[crate::RenderPassColorAttachment {
+                    view: &views[target_mip],
+                    resolve_target: None,
+                    ops: crate::Operations {
+                        load: crate::LoadOp::Clear(crate::Color::TRANSPARENT),
+                        store: true,
+                    },
+                }],
+                depth_stencil_attachment: None,
+            });
+            rpass.set_pipeline(pipeline);
+            rpass.set_bind_group(0, &bind_group, &[]);
+            rpass.draw(0..3, 0..1);
+        }
+    }
+}
+
+/// Encodes render passes filling mip levels `1..mip_count` of `texture` from its top level.
+///
+/// This uses a [`MipmapGenerator`] kept with the device, so its pipelines are created once
+/// per format and reused by later calls. See [`MipmapGenerator::generate`] for the
+/// requirements on the texture.
+pub fn generate_mipmaps(
+    device: &crate::Device,
+    encoder: &mut crate::CommandEncoder,
+    texture: &crate::Texture,
+    format: crate::TextureFormat,
+    mip_count: u32,
+) {
+    device
+        .shared
+        .mipmap_generator
+        .lock()
+        .get_or_insert_with(|| MipmapGenerator::new(device))
+        .generate(device, encoder, texture, format, mip_count);
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    #[cfg(feature = "naga")]
+    fn blit_shader_is_valid() {
+        crate::util::validate_wgsl(include_str!("blit.wgsl")).unwrap();
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn generate_mipmaps_keeps_solid_color() {
+        use super::generate_mipmaps;
+        use crate::util::{read_test_texture, test_device, TextureDescriptorExt};
+        use std::num::NonZeroU32;
+
+        let (device, queue) = match test_device() {
+            Some(device) => device,
+            None => return,
+        };
+        let format = crate::TextureFormat::Rgba8Unorm;
+        let desc = crate::TextureDescriptor::d2(
+            64,
+            64,
+            format,
+            crate::TextureUsage::RENDER_ATTACHMENT
+                | crate::TextureUsage::SAMPLED
+                | crate::TextureUsage::COPY_DST
+                | crate::TextureUsage::COPY_SRC,
+        )
+        .with_mips(7);
+        let texture = device.create_texture(&desc);
+        let color = [51, 102, 204, 255];
+        queue.write_texture(
+            texture.as_image_copy(),
+            &color.repeat(64 * 64),
+            crate::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(64 * 4),
+                rows_per_image: None,
+            },
+            desc.size,
+        );
+
+        let mut encoder =
+            device.create_command_encoder(&crate::CommandEncoderDescriptor { label: None });
+        generate_mipmaps(&device, &mut encoder, &texture, format, 7);
+        queue.submit(Some(encoder.finish()));
+
+        let pixel = read_test_texture(&device, &queue, &texture, 6);
+        assert_eq!(pixel.len(), 4);
+        // Filtering may round differently from one level to the next
+        for (&channel, &expected) in pixel.iter().zip(&color) {
+            assert!((channel as i32 - expected as i32).abs() <= 1, "{:?}", pixel);
+        }
+    }
+}
@@ -1,19 +1,103 @@
 //! Utility structures and functions.
 
+mod adapter;
+mod arena;
+mod atlas;
+mod batch;
 mod belt;
+mod bind;
+mod blend;
+mod bundle;
+mod color;
+mod depth;
 mod device;
+mod downlevel;
 mod encoder;
+mod features;
+mod fullscreen;
+mod indirect;
+#[cfg(feature = "serde")]
+mod info;
+mod limits;
+mod mipmap;
+mod pass;
+mod pipeline;
+mod preprocess;
+mod primitive;
+#[cfg(feature = "bytemuck")]
+mod push;
+#[cfg(feature = "image")]
+mod readback;
+#[cfg(feature = "naga")]
+mod reflect;
+mod shader;
+#[cfg(feature = "bytemuck")]
+mod storage;
+mod surface;
+mod texture;
+mod timer;
+#[cfg(feature = "bytemuck")]
+mod uniform;
+mod vendor;
+mod vertex;
+#[cfg(all(feature = "notify", feature = "naga"))]
+mod watcher;
 
 use std::{
     borrow::Cow,
     future::Future,
     mem::{align_of, size_of},
+    ops::RangeBounds,
     ptr::copy_nonoverlapping,
 };
 
-pub use belt::StagingBelt;
+pub use adapter::{negotiate_device_descriptor, AdapterExt};
+pub use arena::{ArenaAllocation, BufferArena, BufferArenaFull};
+pub use atlas::{AtlasRect, TextureAtlas};
+pub use batch::BufferUpdateBatch;
+pub use belt::{StagingBelt, StagingBeltStats};
+pub use bind::BindGroupPair;
+pub use blend::BlendStateExt;
+#[cfg(not(target_arch = "wasm32"))]
+pub use bundle::ParallelBundleRecorder;
+pub use bundle::{RenderBundleBuilder, RenderBundleTargets};
+pub use color::ColorExt;
+pub use depth::DepthStencilStateExt;
 pub use device::{BufferInitDescriptor, DeviceExt};
+pub use downlevel::{downlevel_report, DownlevelFlagsExt};
 pub use encoder::RenderEncoder;
+pub use features::{FeaturesExt, MissingFeatures, UnknownFeatureName};
+pub use fullscreen::{create_fullscreen_pipeline, fullscreen_triangle_shader};
+pub use indirect::{DrawIndexedIndirectArgs, DrawIndirectArgs};
+#[cfg(feature = "serde")]
+pub use info::SerializableAdapterInfo;
+pub use limits::LimitsExt;
+pub use mipmap::{generate_mipmaps, MipmapGenerator};
+pub use pass::RenderPassBuilder;
+pub use pipeline::{ComputePipelineBuilder, RenderPipelineBuilder};
+pub use preprocess::{preprocess_wgsl, PreprocessError, ShaderWithSourceMap};
+pub use primitive::PrimitiveStateExt;
+#[cfg(feature = "bytemuck")]
+pub use push::push_constants;
+#[cfg(feature = "image")]
+pub use readback::{read_texture_to_image, ReadTextureError};
+#[cfg(feature = "naga")]
+pub use reflect::reflect_bind_group_layouts;
+pub use shader::ShaderLoadError;
+#[cfg(feature = "bytemuck")]
+pub use storage::StorageBuffer;
+pub use surface::{select_present_mode, select_surface_format, SurfaceCapabilitiesSnapshot};
+pub use texture::{
+    create_depth_texture, image_data_layout, is_depth_format, Extent3dExt, Origin3dExt,
+    TextureDescriptorExt, TextureFormatExt,
+};
+pub use timer::{timestamp_delta_ns, GpuTimer, TimestampQueryDisabled};
+#[cfg(feature = "bytemuck")]
+pub use uniform::{DynamicUniformBuffer, DynamicUniformBufferFull, UniformBuffer};
+pub use vendor::AdapterInfoExt;
+pub use vertex::VertexFormatExt;
+#[cfg(all(feature = "notify", feature = "naga"))]
+pub use watcher::ShaderWatcher;
 
 /// Treat the given byte slice as a SPIR-V module.
 ///
@@ -25,6 +109,22 @@ pub use encoder::RenderEncoder;
 /// - Input is longer than [`usize::max_value`]
 /// - SPIR-V magic number is missing from beginning of stream
 pub fn make_spirv(data: &[u8]) -> super::ShaderSource {
+    super::ShaderSource::SpirV(make_spirv_raw(data))
+}
+
+/// Treat the given byte slice as the words of a SPIR-V module.
+///
+/// If `data` is already aligned to 4 bytes, the words borrow from it directly,
+/// otherwise they are copied into an owned vector.
+///
+/// # Panic
+///
+/// This function panics if:
+///
+/// - Input length isn't multiple of 4
+/// - Input is longer than [`usize::max_value`]
+/// - SPIR-V magic number is missing from beginning of stream
+pub fn make_spirv_raw(data: &[u8]) -> Cow<[u32]> {
     const MAGIC_NUMBER: u32 = 0x0723_0203;
 
     assert_eq!(
@@ -53,7 +153,242 @@ pub fn make_spirv(data: &[u8]) -> super::ShaderSource {
         "wrong magic word {:x}. Make sure you are using a binary SPIRV file.",
         words[0]
     );
-    super::ShaderSource::SpirV(words)
+    words
+}
+
+/// Names of the entry points of a SPIR-V module, in declaration order.
+///
+/// A module may declare several entry points, whose names are needed to create pipelines
+/// from it. Instructions are scanned until the end of the module, or the first malformed one.
+///
+/// # Panic
+///
+/// This function panics in the same cases as [`make_spirv_raw`].
+pub fn spirv_entry_points(data: &[u8]) -> Vec<String> {
+    const HEADER_LEN: usize = 5;
+    const OP_ENTRY_POINT: u32 = 15;
+
+    let words = make_spirv_raw(data);
+    let mut names = Vec::new();
+    let mut rest = words.get(HEADER_LEN..).unwrap_or(&[]);
+    while let Some(&first) = rest.first() {
+        let count = (first >> 16) as usize;
+        if count == 0 || count > rest.len() {
+            break;
+        }
+        // OpEntryPoint ExecutionModel EntryPointId Name Interface...
+        if first & 0xffff == OP_ENTRY_POINT && count > 3 {
+            let mut bytes = Vec::with_capacity((count - 3) * size_of::<u32>());
+            for word in &rest[3..count] {
+                bytes.extend_from_slice(&word.to_le_bytes());
+            }
+            let len = bytes
+                .iter()
+                .position(|&byte| byte == 0)
+                .unwrap_or(bytes.len());
+            names.push(String::from_utf8_lossy(&bytes[..len]).into_owned());
+        }
+        rest = &rest[count..];
+    }
+    names
+}
+
+/// Parse and validate a WGSL module on the CPU, without creating a shader module.
+///
+/// This allows catching errors in shader source early, before it is handed to
+/// [`Device::create_shader_module`](crate::Device::create_shader_module).
+/// On failure, the returned string describes the parsing or validation error.
+#[cfg(feature = "naga")]
+pub fn validate_wgsl(source: &str) -> Result<(), String> {
+    let module = naga::front::wgsl::parse_str(source).map_err(|err| err.to_string())?;
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .map_err(|err| err.to_string())?;
+    Ok(())
+}
+
+/// Compile a GLSL shader for the given `stage` into a SPIR-V module through naga.
+///
+/// The entry point of the shader is expected to be `main`.
+///
+/// # Panic
+///
+/// This function panics if:
+///
+/// - `stage` is not exactly one of `VERTEX`, `FRAGMENT` or `COMPUTE`
+/// - The shader fails to parse, validate, or translate to SPIR-V
+#[cfg(feature = "glsl")]
+pub fn make_glsl(source: &str, stage: super::ShaderStage) -> super::ShaderSource<'static> {
+    use naga::{back::spv, front::glsl, valid::Validator};
+
+    let stage = match stage {
+        super::ShaderStage::VERTEX => naga::ShaderStage::Vertex,
+        super::ShaderStage::FRAGMENT => naga::ShaderStage::Fragment,
+        super::ShaderStage::COMPUTE => naga::ShaderStage::Compute,
+        _ => panic!("GLSL shaders must have exactly one stage, got {:?}", stage),
+    };
+    let options = glsl::Options {
+        entry_points: std::iter::once(("main".to_string(), stage)).collect(),
+        defines: Default::default(),
+    };
+    let module = glsl::parse_str(source, &options)
+        .unwrap_or_else(|err| panic!("Failed to parse GLSL: {:?}", err));
+    let info = Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    )
+    .validate(&module)
+    .unwrap_or_else(|err| panic!("Failed to validate GLSL: {:?}", err));
+    let options = spv::Options {
+        lang_version: (1, 0),
+        flags: spv::WriterFlags::empty(),
+        capabilities: None,
+    };
+    let words = spv::write_vec(&module, &info, &options)
+        .unwrap_or_else(|err| panic!("Failed to translate GLSL to SPIR-V: {:?}", err));
+    super::ShaderSource::SpirV(Cow::Owned(words))
+}
+
+/// Describes a shader module built from a module in naga's intermediate representation,
+/// without going through WGSL.
+///
+/// The descriptor has all the [`ShaderFlags`](crate::ShaderFlags) set, like WGSL shaders:
+/// the module is validated by naga according to them when the shader module is created,
+/// and they can be changed on the descriptor beforehand.
+#[cfg(feature = "naga")]
+pub fn shader_from_naga(
+    module: naga::Module,
+    label: Option<&str>,
+) -> super::ShaderModuleDescriptor<'_> {
+    super::ShaderModuleDescriptor {
+        label,
+        source: super::ShaderSource::Naga(module),
+        flags: super::ShaderFlags::all(),
+    }
+}
+
+/// Rounds `value` up to the next multiple of `alignment`, which has to be a power of two.
+pub fn align_to(value: u64, alignment: u64) -> u64 {
+    debug_assert!(
+        alignment.is_power_of_two(),
+        "Alignment {} is not a power of two",
+        alignment
+    );
+    let align_mask = alignment - 1;
+    (value + align_mask) & !align_mask
+}
+
+/// Rounds `value` up to a multiple of [`COPY_BUFFER_ALIGNMENT`](crate::COPY_BUFFER_ALIGNMENT),
+/// as required for buffer sizes and the offsets and sizes of buffer copies.
+pub fn align_to_copy_buffer(value: u64) -> u64 {
+    align_to(value, super::COPY_BUFFER_ALIGNMENT)
+}
+
+/// Parses a comma separated list of backend names, such as `"dx12,vulkan"`.
+///
+/// Recognized names are `vulkan`, `metal`, `dx12`, `dx11`, `gl`, `webgpu`, `primary`,
+/// `secondary` and `all`, in any case. Unknown names are logged and ignored.
+///
+/// Returns `None` if the list doesn't contain a single recognized name.
+pub fn parse_backend_bits(names: &str) -> Option<super::BackendBit> {
+    names
+        .split(',')
+        .map(|name| name.trim())
+        .filter(|name| !name.is_empty())
+        .fold(None, |bits, name| {
+            let bit = match name.to_lowercase().as_str() {
+                "vulkan" => super::BackendBit::VULKAN,
+                "metal" => super::BackendBit::METAL,
+                "dx12" => super::BackendBit::DX12,
+                "dx11" => super::BackendBit::DX11,
+                "gl" => super::BackendBit::GL,
+                "webgpu" => super::BackendBit::BROWSER_WEBGPU,
+                "primary" => super::BackendBit::PRIMARY,
+                "secondary" => super::BackendBit::SECONDARY,
+                "all" => super::BackendBit::all(),
+                other => {
+                    log::warn!("Ignoring unknown backend {:?}", other);
+                    return bits;
+                }
+            };
+            Some(bits.unwrap_or_else(super::BackendBit::empty) | bit)
+        })
+}
+
+/// Reads the backends to use from the `WGPU_BACKEND` environment variable,
+/// see [`parse_backend_bits`] for the syntax.
+///
+/// Returns `None` if the variable is not set, or if it names no known backend, in which
+/// case a warning is logged.
+pub fn backend_bits_from_env() -> Option<super::BackendBit> {
+    let names = std::env::var("WGPU_BACKEND").ok()?;
+    let bits = parse_backend_bits(&names);
+    if bits.is_none() {
+        log::warn!(
+            "Ignoring WGPU_BACKEND {:?}, it names no known backend",
+            names
+        );
+    }
+    bits
+}
+
+/// Parses a power preference name, either `low` or `high` in any case.
+///
+/// Returns `None` for any other name.
+pub fn parse_power_preference(name: &str) -> Option<super::PowerPreference> {
+    match name.trim().to_lowercase().as_str() {
+        "low" => Some(super::PowerPreference::LowPower),
+        "high" => Some(super::PowerPreference::HighPerformance),
+        _ => None,
+    }
+}
+
+/// Reads the power preference from the `WGPU_POWER_PREF` environment variable,
+/// see [`parse_power_preference`] for the syntax.
+///
+/// Returns `None` if the variable is not set, or if it holds an unknown name, in which
+/// case a warning is logged.
+pub fn power_preference_from_env() -> Option<super::PowerPreference> {
+    let name = std::env::var("WGPU_POWER_PREF").ok()?;
+    let power_preference = parse_power_preference(&name);
+    if power_preference.is_none() {
+        log::warn!("Ignoring unknown power preference {:?}", name);
+    }
+    power_preference
+}
+
+/// Picks the adapter named by the `WGPU_ADAPTER_NAME` environment variable, to run on a
+/// known GPU when several are available, such as in CI.
+///
+/// The first adapter of `backends` whose name contains the variable, ignoring case, is
+/// returned. If `WGPU_POWER_PREF` is set as well, the matching adapter best suiting it is
+/// picked instead, as with [`Instance::find_adapter`](super::Instance::find_adapter).
+///
+/// Returns `None` if the variable is not set or no adapter matches, so the caller can fall
+/// back to [`Instance::request_adapter`](super::Instance::request_adapter).
+#[cfg(not(target_arch = "wasm32"))]
+pub fn initialize_adapter_from_env(
+    instance: &super::Instance,
+    backends: super::BackendBit,
+) -> Option<super::Adapter> {
+    let filter = std::env::var("WGPU_ADAPTER_NAME").ok()?;
+    let mut matching = instance
+        .enumerate_adapters(backends)
+        .filter(|adapter| adapter_name_matches(&adapter.get_info().name, &filter));
+    match power_preference_from_env() {
+        Some(power_preference) => matching.min_by_key(|adapter| {
+            super::adapter_rank(adapter.get_info().device_type, power_preference)
+        }),
+        None => matching.next(),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn adapter_name_matches(name: &str, filter: &str) -> bool {
+    name.to_lowercase().contains(&filter.to_lowercase())
 }
 
 /// CPU accessible buffer used to download data back from the GPU.
@@ -94,9 +429,462 @@ impl DownloadBuffer {
     }
 }
 
+/// Read back the contents of a mappable buffer.
+///
+/// Maps `bounds` of the buffer for reading, copies the contents out and unmaps it again.
+/// The device is polled while waiting, so no external poller needs to be running.
+///
+/// # Panics
+///
+/// - `buffer` was not created with [`BufferUsage::MAP_READ`](crate::BufferUsage::MAP_READ).
+/// - `buffer` is already mapped.
+pub fn read_buffer<'a, S: RangeBounds<super::BufferAddress>>(
+    device: &super::Device,
+    buffer: &'a super::Buffer,
+    bounds: S,
+) -> impl Future<Output = Result<Vec<u8>, super::BufferAsyncError>> + Send + 'a {
+    assert!(
+        buffer.usage.contains(super::BufferUsage::MAP_READ),
+        "Buffer {:?} must have MAP_READ usage to be read back",
+        buffer.id
+    );
+
+    let slice = buffer.slice(bounds);
+    let map = slice.map_async(super::MapMode::Read);
+    device.poll(super::Maintain::Wait);
+    async move {
+        if let Err(err) = map.await {
+            // The buffer never got mapped, so only the mapping state needs to be cleared
+            buffer.map_context.lock().reset();
+            return Err(err);
+        }
+        let data = slice.get_mapped_range().to_vec();
+        buffer.unmap();
+        Ok(data)
+    }
+}
+
 impl std::ops::Deref for DownloadBuffer {
     type Target = [u8];
     fn deref(&self) -> &[u8] {
         super::BufferMappedRangeSlice::slice(&self.1)
     }
 }
+
+/// Submits `command_buffers` to `queue`, calling `callback` once this submission is done
+/// executing, regardless of the submissions made after it.
+///
+/// See [`Queue::on_submitted_work_done`](crate::Queue::on_submitted_work_done) for when the
+/// callback runs.
+///
+/// # Panics
+///
+/// Panics if `queue` wasn't created with `device`.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn submit_with_completion<I: IntoIterator<Item = super::CommandBuffer>>(
+    queue: &super::Queue,
+    device: &super::Device,
+    command_buffers: I,
+    callback: impl FnOnce() + Send + 'static,
+) -> super::SubmissionIndex {
+    assert!(
+        std::sync::Arc::ptr_eq(&queue.device, &device.shared),
+        "Queue wasn't created with the device"
+    );
+    let index = queue.submit(command_buffers);
+    queue.on_submission_done(index, callback);
+    index
+}
+
+/// Blocks until all pending GPU work and map callbacks have been processed.
+///
+/// [`Device::poll`](crate::Device::poll) with [`Maintain::Poll`](crate::Maintain::Poll) only
+/// processes the work that already completed, so a callback passed to `map_async` may not
+/// run yet. After this returns, every buffer mapped before the call is mapped, and its
+/// mapped range can be accessed right away:
+///
+/// ```ignore
+/// let slice = buffer.slice(..);
+/// let map = slice.map_async(wgpu::MapMode::Read);
+/// wgpu::util::wait_for_maps(&device);
+/// pollster::block_on(map).unwrap();
+/// let data = slice.get_mapped_range();
+/// ```
+///
+/// On the web blocking is impossible, so this returns immediately: the browser polls the
+/// device on its own, and the map futures have to be awaited instead.
+pub fn wait_for_maps(device: &super::Device) {
+    device.poll(super::Maintain::Wait);
+}
+
+/// Schedules writing the concatenation of `parts` into `buffer`, starting at `offset`.
+///
+/// Each part is written with its own [`Queue::write_buffer`](crate::Queue::write_buffer)
+/// call at a running offset, so they don't have to be copied into a single slice first.
+///
+/// # Panics
+///
+/// - `offset` or the length of a part is not a multiple of
+///   [`COPY_BUFFER_ALIGNMENT`](crate::COPY_BUFFER_ALIGNMENT).
+/// - The parts don't fit in `buffer` after `offset`.
+pub fn write_buffer_gather(
+    queue: &super::Queue,
+    buffer: &super::Buffer,
+    offset: super::BufferAddress,
+    parts: &[&[u8]],
+) {
+    check_gather(buffer.size(), offset, parts);
+    let mut offset = offset;
+    for part in parts {
+        if !part.is_empty() {
+            queue.write_buffer(buffer, offset, part);
+        }
+        offset += part.len() as super::BufferAddress;
+    }
+}
+
+/// Checks the parts of a [`write_buffer_gather`], returning their total size.
+fn check_gather(
+    buffer_size: super::BufferAddress,
+    offset: super::BufferAddress,
+    parts: &[&[u8]],
+) -> super::BufferAddress {
+    assert_eq!(
+        offset % super::COPY_BUFFER_ALIGNMENT,
+        0,
+        "Write offset {} is not a multiple of {}",
+        offset,
+        super::COPY_BUFFER_ALIGNMENT
+    );
+    let mut total = 0;
+    for (index, part) in parts.iter().enumerate() {
+        let len = part.len() as super::BufferAddress;
+        assert_eq!(
+            len % super::COPY_BUFFER_ALIGNMENT,
+            0,
+            "Part {} of {} bytes is not a multiple of {}",
+            index,
+            len,
+            super::COPY_BUFFER_ALIGNMENT
+        );
+        total += len;
+    }
+    assert!(
+        offset + total <= buffer_size,
+        "Write of {} bytes at offset {} overruns the buffer of size {}",
+        total,
+        offset,
+        buffer_size
+    );
+    total
+}
+
+/// A waker that does nothing, for polling futures driven by
+/// [`Device::poll`](crate::Device::poll) rather than by an executor.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn noop_waker() -> std::task::Waker {
+    use std::task::{RawWaker, RawWakerVTable, Waker};
+
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    // Safety: the vtable functions ignore the data pointer, so they uphold its contract.
+    unsafe { Waker::from_raw(clone(std::ptr::null())) }
+}
+
+/// Device and queue of the default adapter, for the tests that need a GPU. These pass
+/// without doing anything when `None` is returned, as there is no adapter.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub(crate) fn test_device() -> Option<(super::Device, super::Queue)> {
+    let instance = super::Instance::new(super::BackendBit::PRIMARY);
+    let adapter =
+        pollster::block_on(instance.request_adapter(&super::RequestAdapterOptions::default()))?;
+    pollster::block_on(adapter.request_device(
+        &super::DeviceDescriptor {
+            label: None,
+            features: super::Features::empty(),
+            limits: super::Limits::default(),
+        },
+        None,
+    ))
+    .ok()
+}
+
+/// Reads back `mip_level` of a 2D texture with an uncompressed format, for tests. The rows
+/// of texels are packed tightly.
+#[cfg(all(test, not(target_arch = "wasm32")))]
+pub(crate) fn read_test_texture(
+    device: &super::Device,
+    queue: &super::Queue,
+    texture: &super::Texture,
+    mip_level: u32,
+) -> Vec<u8> {
+    let size = texture
+        .size()
+        .mip_level_size(mip_level, texture.dimension());
+    let (layout, padded_bytes_per_row) =
+        image_data_layout(texture.format(), size.width, size.height);
+    let buffer = device.create_buffer(&super::BufferDescriptor {
+        label: Some("texture readback"),
+        size: padded_bytes_per_row as super::BufferAddress * size.height as super::BufferAddress,
+        usage: super::BufferUsage::COPY_DST | super::BufferUsage::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder =
+        device.create_command_encoder(&super::CommandEncoderDescriptor { label: None });
+    encoder.copy_texture_to_buffer(
+        texture.as_image_copy_mip(mip_level, super::Origin3d::ZERO),
+        super::ImageCopyBuffer {
+            buffer: &buffer,
+            layout,
+        },
+        super::Extent3d {
+            depth_or_array_layers: 1,
+            ..size
+        },
+    );
+    queue.submit(Some(encoder.finish()));
+
+    let data = pollster::block_on(read_buffer(device, &buffer, ..)).unwrap();
+    let bytes_per_row = size.width as usize * texture.format().describe().block_size as usize;
+    data.chunks(padded_bytes_per_row as usize)
+        .flat_map(|row| &row[..bytes_per_row])
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, future::Future};
+
+    const MAGIC_NUMBER: u32 = 0x0723_0203;
+
+    #[test]
+    fn align_to_aligned() {
+        assert_eq!(super::align_to(256, 256), 256);
+        assert_eq!(super::align_to(12, 4), 12);
+        assert_eq!(super::align_to(7, 1), 7);
+    }
+
+    #[test]
+    fn align_to_rounds_up() {
+        assert_eq!(super::align_to(1, 256), 256);
+        assert_eq!(super::align_to(257, 256), 512);
+        assert_eq!(super::align_to_copy_buffer(5), 8);
+        assert_eq!(super::align_to_copy_buffer(3), 4);
+    }
+
+    #[test]
+    fn align_to_zero() {
+        assert_eq!(super::align_to(0, 256), 0);
+        assert_eq!(super::align_to_copy_buffer(0), 0);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not a power of two")]
+    fn align_to_non_power_of_two() {
+        super::align_to(10, 3);
+    }
+
+    #[test]
+    fn gather_parts_fit() {
+        let header = [1u8; 16];
+        let body = [2u8; 48];
+        assert_eq!(super::check_gather(64, 0, &[&header, &body]), 64);
+        assert_eq!(super::check_gather(128, 64, &[&header, &[], &body]), 64);
+    }
+
+    #[test]
+    #[should_panic(expected = "overruns the buffer")]
+    fn gather_parts_overrun() {
+        super::check_gather(64, 16, &[&[0; 16], &[0; 48]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Part 1 of 6 bytes")]
+    fn gather_parts_unaligned() {
+        super::check_gather(64, 0, &[&[0; 8], &[0; 6]]);
+    }
+
+    #[test]
+    fn noop_waker_polls_ready_future() {
+        let waker = super::noop_waker();
+        let mut cx = std::task::Context::from_waker(&waker);
+        let mut ready = Box::pin(async { 42 });
+        assert_eq!(ready.as_mut().poll(&mut cx), std::task::Poll::Ready(42));
+        // Cloning and waking must not touch the null data pointer.
+        let other = waker.clone();
+        waker.wake_by_ref();
+        other.wake();
+    }
+
+    #[test]
+    fn make_spirv_raw_borrows_aligned_data() {
+        let words = [MAGIC_NUMBER, 0];
+        let bytes = unsafe { std::slice::from_raw_parts(words.as_ptr() as *const u8, 8) };
+        match super::make_spirv_raw(bytes) {
+            Cow::Borrowed(borrowed) => assert_eq!(borrowed.as_ptr(), words.as_ptr()),
+            Cow::Owned(_) => panic!("aligned data should be borrowed"),
+        }
+    }
+
+    #[test]
+    fn make_spirv_raw_copies_unaligned_data() {
+        let mut words = [0u32; 3];
+        let bytes = unsafe { std::slice::from_raw_parts_mut(words.as_mut_ptr() as *mut u8, 12) };
+        bytes[1..5].copy_from_slice(&MAGIC_NUMBER.to_ne_bytes());
+        match super::make_spirv_raw(&bytes[1..9]) {
+            Cow::Borrowed(_) => panic!("unaligned data should be copied"),
+            Cow::Owned(owned) => assert_eq!(owned, [MAGIC_NUMBER, 0]),
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn make_spirv_raw_panics_without_magic_number() {
+        super::make_spirv_raw(&[0; 8]);
+    }
+
+    #[test]
+    fn spirv_entry_points_in_order() {
+        // Literal strings are packed little endian, and null terminated
+        fn entry_point(model: u32, id: u32, name: &str) -> Vec<u32> {
+            let mut bytes = name.as_bytes().to_vec();
+            bytes.resize(name.len() / 4 * 4 + 4, 0);
+            let mut words = vec![0, model, id];
+            words.extend(
+                bytes
+                    .chunks(4)
+                    .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]])),
+            );
+            words[0] = (words.len() as u32) << 16 | 15;
+            words
+        }
+
+        let mut words = vec![MAGIC_NUMBER, 0x0001_0000, 0, 3, 0];
+        // OpCapability Shader
+        words.extend(&[2 << 16 | 17, 1]);
+        words.extend(entry_point(0, 1, "vs_main"));
+        words.extend(entry_point(4, 2, "main"));
+        let mut bytes = Vec::new();
+        for word in &words {
+            bytes.extend_from_slice(&word.to_ne_bytes());
+        }
+
+        assert_eq!(super::spirv_entry_points(&bytes), ["vs_main", "main"]);
+        assert!(super::spirv_entry_points(&bytes[..28]).is_empty());
+    }
+
+    #[cfg(feature = "naga")]
+    #[test]
+    fn validate_wgsl_reports_errors() {
+        assert!(
+            super::validate_wgsl(include_str!("../../examples/hello-compute/shader.wgsl")).is_ok()
+        );
+
+        let err = super::validate_wgsl("bogus_token fn main() {}").unwrap_err();
+        assert!(err.contains("bogus_token"), "unexpected error: {}", err);
+    }
+
+    #[cfg(feature = "naga")]
+    #[test]
+    fn shader_from_naga_compute_module() {
+        let mut module = naga::Module::default();
+        module.entry_points.push(naga::EntryPoint {
+            name: "main".to_string(),
+            stage: naga::ShaderStage::Compute,
+            early_depth_test: None,
+            workgroup_size: [64, 1, 1],
+            function: naga::Function::default(),
+        });
+
+        let desc = super::shader_from_naga(module, Some("generated"));
+        assert_eq!(desc.label, Some("generated"));
+        assert_eq!(desc.flags, crate::ShaderFlags::all());
+        let module = match desc.source {
+            crate::ShaderSource::Naga(ref module) => module,
+            _ => panic!("Expected a naga shader source"),
+        };
+
+        let words = crate::naga_to_spirv(module, desc.flags).unwrap();
+        let mut bytes = Vec::with_capacity(words.len() * 4);
+        for word in &words {
+            bytes.extend_from_slice(&word.to_ne_bytes());
+        }
+        assert_eq!(super::spirv_entry_points(&bytes), ["main"]);
+
+        let mut invalid = naga::Module::default();
+        invalid.types.append(naga::Type {
+            name: None,
+            inner: naga::TypeInner::Scalar {
+                kind: naga::ScalarKind::Float,
+                width: 3,
+            },
+        });
+        assert!(crate::naga_to_spirv(&invalid, crate::ShaderFlags::VALIDATION).is_err());
+    }
+
+    #[test]
+    fn parse_backend_bits_list() {
+        use crate::BackendBit;
+
+        assert_eq!(
+            super::parse_backend_bits("dx12,vulkan"),
+            Some(BackendBit::DX12 | BackendBit::VULKAN)
+        );
+        assert_eq!(
+            super::parse_backend_bits(" Metal , bogus,"),
+            Some(BackendBit::METAL)
+        );
+        assert_eq!(super::parse_backend_bits("all"), Some(BackendBit::all()));
+        assert_eq!(super::parse_backend_bits("vulkn"), None);
+        assert_eq!(super::parse_backend_bits(" , "), None);
+    }
+
+    #[test]
+    fn parse_power_preference() {
+        use crate::PowerPreference;
+
+        assert_eq!(
+            super::parse_power_preference("High"),
+            Some(PowerPreference::HighPerformance)
+        );
+        assert_eq!(
+            super::parse_power_preference(" low "),
+            Some(PowerPreference::LowPower)
+        );
+        assert_eq!(super::parse_power_preference("fast"), None);
+        assert_eq!(super::parse_power_preference(""), None);
+    }
+
+    #[test]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn adapter_name_matches() {
+        let names = [
+            "NVIDIA GeForce RTX 3080",
+            "Intel(R) UHD Graphics 630",
+            "llvmpipe (LLVM 12.0.0, 256 bits)",
+        ];
+
+        assert_eq!(
+            names
+                .iter()
+                .position(|name| super::adapter_name_matches(name, "uhd graphics")),
+            Some(1)
+        );
+        assert_eq!(
+            names
+                .iter()
+                .position(|name| super::adapter_name_matches(name, "LLVMPIPE")),
+            Some(2)
+        );
+        assert!(super::adapter_name_matches("GeForce", ""));
+        assert!(!names
+            .iter()
+            .any(|name| super::adapter_name_matches(name, "radeon")));
+    }
+}
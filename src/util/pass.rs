@@ -0,0 +1,104 @@
+use crate::{
+    Color, CommandEncoder, Label, LoadOp, Operations, RenderPass, RenderPassColorAttachment,
+    RenderPassDepthStencilAttachment, RenderPassDescriptor, TextureView,
+};
+
+/// Builds the [`RenderPassDescriptor`] of common render passes.
+///
+/// Color attachments are added in order, and always stored. For anything else, such as
+/// resolve targets or stencil operations, use the descriptor directly.
+///
+/// ```ignore
+/// let mut pass = RenderPassBuilder::new()
+///     .clear_color(&frame.view, wgpu::Color::BLACK)
+///     .depth(&depth_view, 1.0)
+///     .begin(&mut encoder);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RenderPassBuilder<'a> {
+    label: Label<'a>,
+    color_attachments: Vec<RenderPassColorAttachment<'a>>,
+    depth_stencil_attachment: Option<RenderPassDepthStencilAttachment<'a>>,
+}
+
+impl<'a> RenderPassBuilder<'a> {
+    /// Creates a builder for an unlabeled pass without attachments.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the debug label of the pass.
+    pub fn label(self, label: &'a str) -> Self {
+        Self {
+            label: Some(label),
+            ..self
+        }
+    }
+
+    /// Adds a color attachment cleared to `color` at the start of the pass.
+    pub fn clear_color(self, view: &'a TextureView, color: Color) -> Self {
+        self.color(view, LoadOp::Clear(color))
+    }
+
+    /// Adds a color attachment keeping its previous contents.
+    pub fn load_color(self, view: &'a TextureView) -> Self {
+        self.color(view, LoadOp::Load)
+    }
+
+    /// Sets the depth attachment, cleared to `clear_depth` at the start of the pass.
+    ///
+    /// The stencil aspect, if any, is left untouched.
+    pub fn depth(self, view: &'a TextureView, clear_depth: f32) -> Self {
+        Self {
+            depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+                view,
+                depth_ops: Some(Operations {
+                    load: LoadOp::Clear(clear_depth),
+                    store: true,
+                }),
+                stencil_ops: None,
+            }),
+            ..self
+        }
+    }
+
+    fn color(mut self, view: &'a TextureView, load: LoadOp<Color>) -> Self {
+        self.color_attachments.push(RenderPassColorAttachment {
+            view,
+            resolve_target: None,
+            ops: Operations { load, store: true },
+        });
+        self
+    }
+
+    /// Describes the pass, borrowing the attachments from the builder.
+    pub fn descriptor(&self) -> RenderPassDescriptor<'a, '_> {
+        RenderPassDescriptor {
+            label: self.label,
+            color_attachments: &self.color_attachments,
+            depth_stencil_attachment: self.depth_stencil_attachment.clone(),
+        }
+    }
+
+    /// Begins the pass on `encoder`.
+    pub fn begin<'e>(&self, encoder: &'e mut CommandEncoder) -> RenderPass<'e>
+    where
+        'a: 'e,
+    {
+        encoder.begin_render_pass(&self.descriptor())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderPassBuilder;
+
+    #[test]
+    fn render_pass_builder_without_attachments() {
+        let builder = RenderPassBuilder::new().label("empty");
+        let desc = builder.descriptor();
+        assert_eq!(desc.label, Some("empty"));
+        assert!(desc.color_attachments.is_empty());
+        assert!(desc.depth_stencil_attachment.is_none());
+    }
+}
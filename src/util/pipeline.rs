@@ -0,0 +1,204 @@
+use crate::{
+    ColorTargetState, ComputePipeline, ComputePipelineDescriptor, DepthStencilState, Device,
+    FragmentState, Label, MultisampleState, PipelineLayout, PrimitiveState, RenderPipeline,
+    RenderPipelineDescriptor, ShaderModule, VertexBufferLayout, VertexState,
+};
+
+/// Builds a [`RenderPipelineDescriptor`], with defaults for everything but the vertex stage.
+///
+/// Unless set, the pipeline has no fragment stage and no depth stencil state, and the
+/// layout is derived from the shaders. Primitive and multisample states default to
+/// [`PrimitiveState::default`] and [`MultisampleState::default`].
+///
+/// The builder only borrows the shader modules, layouts and targets, which have to outlive it:
+///
+/// ```ignore
+/// let pipeline = RenderPipelineBuilder::new()
+///     .label("scene")
+///     .vertex(&shader, "vs_main", &[Vertex::layout()])
+///     .fragment(&shader, "fs_main", &[swap_chain_format.into()])
+///     .depth_stencil(wgpu::DepthStencilState::depth_write(wgpu::TextureFormat::Depth32Float))
+///     .build(&device);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RenderPipelineBuilder<'a> {
+    label: Label<'a>,
+    layout: Option<&'a PipelineLayout>,
+    vertex: Option<VertexState<'a>>,
+    fragment: Option<FragmentState<'a>>,
+    primitive: PrimitiveState,
+    depth_stencil: Option<DepthStencilState>,
+    multisample: MultisampleState,
+}
+
+impl<'a> RenderPipelineBuilder<'a> {
+    /// Creates a builder for an unlabeled pipeline without any stage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the debug label of the pipeline.
+    pub fn label(self, label: &'a str) -> Self {
+        Self {
+            label: Some(label),
+            ..self
+        }
+    }
+
+    /// Sets the pipeline layout, instead of deriving it from the shaders.
+    pub fn layout(self, layout: &'a PipelineLayout) -> Self {
+        Self {
+            layout: Some(layout),
+            ..self
+        }
+    }
+
+    /// Sets the vertex stage and the layout of its vertex buffers.
+    pub fn vertex(
+        self,
+        module: &'a ShaderModule,
+        entry_point: &'a str,
+        buffers: &'a [VertexBufferLayout<'a>],
+    ) -> Self {
+        Self {
+            vertex: Some(VertexState {
+                module,
+                entry_point,
+                buffers,
+            }),
+            ..self
+        }
+    }
+
+    /// Sets the fragment stage and its color targets.
+    pub fn fragment(
+        self,
+        module: &'a ShaderModule,
+        entry_point: &'a str,
+        targets: &'a [ColorTargetState],
+    ) -> Self {
+        Self {
+            fragment: Some(FragmentState {
+                module,
+                entry_point,
+                targets,
+            }),
+            ..self
+        }
+    }
+
+    /// Sets the primitive assembly and rasterization state.
+    pub fn primitive(self, primitive: PrimitiveState) -> Self {
+        Self { primitive, ..self }
+    }
+
+    /// Sets the depth stencil state.
+    pub fn depth_stencil(self, depth_stencil: DepthStencilState) -> Self {
+        Self {
+            depth_stencil: Some(depth_stencil),
+            ..self
+        }
+    }
+
+    /// Sets the multisample state.
+    pub fn multisample(self, multisample: MultisampleState) -> Self {
+        Self {
+            multisample,
+            ..self
+        }
+    }
+
+    /// Describes the pipeline.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex stage wasn't set.
+    pub fn descriptor(&self) -> RenderPipelineDescriptor<'a> {
+        RenderPipelineDescriptor {
+            label: self.label,
+            layout: self.layout,
+            vertex: self
+                .vertex
+                .clone()
+                .expect("Render pipeline builder has no vertex stage"),
+            primitive: self.primitive,
+            depth_stencil: self.depth_stencil.clone(),
+            multisample: self.multisample,
+            fragment: self.fragment.clone(),
+        }
+    }
+
+    /// Creates the pipeline on `device`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the vertex stage wasn't set.
+    pub fn build(&self, device: &Device) -> RenderPipeline {
+        device.create_render_pipeline(&self.descriptor())
+    }
+}
+
+/// Builds a [`ComputePipelineDescriptor`], deriving the layout from the shader unless set.
+#[derive(Clone, Debug)]
+pub struct ComputePipelineBuilder<'a> {
+    label: Label<'a>,
+    layout: Option<&'a PipelineLayout>,
+    module: &'a ShaderModule,
+    entry_point: &'a str,
+}
+
+impl<'a> ComputePipelineBuilder<'a> {
+    /// Creates a builder for an unlabeled pipeline running `entry_point` of `module`.
+    pub fn new(module: &'a ShaderModule, entry_point: &'a str) -> Self {
+        Self {
+            label: None,
+            layout: None,
+            module,
+            entry_point,
+        }
+    }
+
+    /// Sets the debug label of the pipeline.
+    pub fn label(self, label: &'a str) -> Self {
+        Self {
+            label: Some(label),
+            ..self
+        }
+    }
+
+    /// Sets the pipeline layout, instead of deriving it from the shader.
+    pub fn layout(self, layout: &'a PipelineLayout) -> Self {
+        Self {
+            layout: Some(layout),
+            ..self
+        }
+    }
+
+    /// Describes the pipeline.
+    pub fn descriptor(&self) -> ComputePipelineDescriptor<'a> {
+        ComputePipelineDescriptor {
+            label: self.label,
+            layout: self.layout,
+            module: self.module,
+            entry_point: self.entry_point,
+        }
+    }
+
+    /// Creates the pipeline on `device`.
+    pub fn build(&self, device: &Device) -> ComputePipeline {
+        device.create_compute_pipeline(&self.descriptor())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RenderPipelineBuilder;
+
+    #[test]
+    #[should_panic(expected = "no vertex stage")]
+    fn render_pipeline_builder_requires_vertex() {
+        RenderPipelineBuilder::new()
+            .label("incomplete")
+            .descriptor();
+    }
+}
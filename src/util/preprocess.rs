@@ -0,0 +1,410 @@
+use std::{
+    collections::HashSet,
+    error,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+const INCLUDE_DIRECTIVE: &str = "// #include";
+const LINE_DIRECTIVE: &str = "// #line";
+
+/// Error returned by [`preprocess_wgsl`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum PreprocessError {
+    /// A source file could not be read.
+    Io {
+        /// File that failed to be read.
+        path: PathBuf,
+        /// Kind of the underlying IO error.
+        kind: io::ErrorKind,
+    },
+    /// An `#include` directive is not followed by a quoted path.
+    MalformedInclude {
+        /// File containing the directive.
+        path: PathBuf,
+        /// Line of the directive, starting at 1.
+        line: usize,
+    },
+    /// A file includes itself, directly or not.
+    ///
+    /// Holds the chain of includes, starting and ending with the same file.
+    IncludeCycle(Vec<PathBuf>),
+}
+
+impl Display for PreprocessError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Io { ref path, kind } => {
+                write!(f, "Failed to read {}: {:?}", path.display(), kind)
+            }
+            Self::MalformedInclude { ref path, line } => write!(
+                f,
+                "{}:{}: expected a quoted path after `#include`",
+                path.display(),
+                line
+            ),
+            Self::IncludeCycle(ref chain) => {
+                write!(f, "Include cycle: ")?;
+                for (i, path) in chain.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, " -> ")?;
+                    }
+                    write!(f, "{}", path.display())?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl error::Error for PreprocessError {}
+
+/// Loads a WGSL file, resolving its `// #include "other.wgsl"` directives.
+///
+/// Included paths are relative to the including file, and are resolved recursively.
+/// Each file is only inserted once, at its first include, so shared files can be
+/// included from everywhere they are needed.
+///
+/// The output is annotated with `// #line N "path"` comments wherever the source file
+/// changes, so that line numbers in shader errors can be traced back to the original
+/// files.
+pub fn preprocess_wgsl(entry_path: &Path) -> Result<String, PreprocessError> {
+    let mut preprocessor = Preprocessor {
+        output: String::new(),
+        included: HashSet::new(),
+        stack: Vec::new(),
+    };
+    preprocessor.include(entry_path)?;
+    Ok(preprocessor.output)
+}
+
+struct Preprocessor {
+    output: String,
+    included: HashSet<PathBuf>,
+    // Files currently being processed, to detect cycles
+    stack: Vec<PathBuf>,
+}
+
+impl Preprocessor {
+    fn include(&mut self, path: &Path) -> Result<(), PreprocessError> {
+        let io_error = |err: io::Error| PreprocessError::Io {
+            path: path.to_owned(),
+            kind: err.kind(),
+        };
+        let path = fs::canonicalize(path).map_err(io_error)?;
+        if self.stack.contains(&path) {
+            let mut chain = self.stack.clone();
+            chain.push(path);
+            return Err(PreprocessError::IncludeCycle(chain));
+        }
+        if !self.included.insert(path.clone()) {
+            return Ok(());
+        }
+        let source = fs::read_to_string(&path).map_err(io_error)?;
+
+        self.mark_line(1, &path);
+        self.stack.push(path);
+        for (index, line) in source.lines().enumerate() {
+            let directive = match line.trim_start().strip_prefix(INCLUDE_DIRECTIVE) {
+                Some(directive) => directive,
+                None => {
+                    self.output.push_str(line);
+                    self.output.push('\n');
+                    continue;
+                }
+            };
+
+            let path = self.stack.last().unwrap().clone();
+            let included =
+                parse_quoted(directive).ok_or_else(|| PreprocessError::MalformedInclude {
+                    path: path.clone(),
+                    line: index + 1,
+                })?;
+            let parent = path.parent().unwrap_or_else(|| Path::new(""));
+            self.include(&parent.join(included))?;
+            // Resume the line numbering of the including file
+            self.mark_line(index + 2, &path);
+        }
+        self.stack.pop();
+        Ok(())
+    }
+
+    fn mark_line(&mut self, line: usize, path: &Path) {
+        self.output.push_str(&format!(
+            "{} {} \"{}\"\n",
+            LINE_DIRECTIVE,
+            line,
+            path.display()
+        ));
+    }
+}
+
+/// Shader source concatenated from several segments, such as a prelude and a user file,
+/// remembering where each one starts.
+///
+/// Errors reported against the concatenated source can then be traced back to the right
+/// segment with [`map_error_line`](Self::map_error_line).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ShaderWithSourceMap {
+    source: String,
+    line_count: u32,
+    segments: Vec<Segment>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Segment {
+    /// First line of the segment in the concatenated source.
+    start: u32,
+    /// Name of the file the segment comes from.
+    name: String,
+    /// Line of the file the segment starts at.
+    line: u32,
+}
+
+impl ShaderWithSourceMap {
+    /// Creates an empty source.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the output of [`preprocess_wgsl`] back to the included files, following its
+    /// `// #line` markers.
+    pub fn from_preprocessed(output: &str) -> Self {
+        let mut map = Self::new();
+        for line in output.lines() {
+            map.push_line(line);
+            if let Some((line, name)) = line
+                .strip_prefix(LINE_DIRECTIVE)
+                .and_then(parse_line_directive)
+            {
+                map.segments.push(Segment {
+                    start: map.line_count + 1,
+                    name: name.to_owned(),
+                    line,
+                });
+            }
+        }
+        map
+    }
+
+    /// Appends the `source` of the file `name`.
+    pub fn push(&mut self, name: impl Into<String>, source: &str) {
+        self.segments.push(Segment {
+            start: self.line_count + 1,
+            name: name.into(),
+            line: 1,
+        });
+        for line in source.lines() {
+            self.push_line(line);
+        }
+    }
+
+    fn push_line(&mut self, line: &str) {
+        self.source.push_str(line);
+        self.source.push('\n');
+        self.line_count += 1;
+    }
+
+    /// The concatenated source.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Maps a line of the concatenated source, starting at 1, to the name of its file and
+    /// its line in that file.
+    ///
+    /// Lines before the first segment keep their number, with an empty file name.
+    pub fn map_error_line(&self, reported_line: u32) -> (String, u32) {
+        match self
+            .segments
+            .iter()
+            .rev()
+            .find(|segment| segment.start <= reported_line)
+        {
+            Some(segment) => (
+                segment.name.clone(),
+                segment.line + (reported_line - segment.start),
+            ),
+            None => (String::new(), reported_line),
+        }
+    }
+}
+
+/// Parses the `N "path"` following a `#line` directive.
+fn parse_line_directive(directive: &str) -> Option<(u32, &str)> {
+    let directive = directive.trim_start();
+    let end = directive.find(' ')?;
+    let line = directive[..end].parse().ok()?;
+    Some((line, parse_quoted(&directive[end..])?))
+}
+
+fn parse_quoted(directive: &str) -> Option<&str> {
+    let rest = directive.trim().strip_prefix('"')?;
+    let end = rest.find('"')?;
+    if !rest[end + 1..].trim().is_empty() {
+        return None;
+    }
+    Some(&rest[..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{preprocess_wgsl, PreprocessError, ShaderWithSourceMap};
+    use std::{fs, path::PathBuf};
+
+    /// Writes the given files into a fresh temporary directory.
+    fn write_files(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("wgpu-preprocess-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for &(path, source) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, source).unwrap();
+        }
+        fs::canonicalize(dir).unwrap()
+    }
+
+    /// Strips the `#line` markers from the output.
+    fn code(output: &str) -> Vec<&str> {
+        output
+            .lines()
+            .filter(|line| !line.starts_with("// #line"))
+            .collect()
+    }
+
+    #[test]
+    fn simple_include() {
+        let dir = write_files(
+            "simple",
+            &[
+                ("main.wgsl", "// #include \"common.wgsl\"\nfn main() {}"),
+                ("common.wgsl", "fn common() {}"),
+            ],
+        );
+        let output = preprocess_wgsl(&dir.join("main.wgsl")).unwrap();
+        assert_eq!(code(&output), ["fn common() {}", "fn main() {}"]);
+        assert!(output.contains(&format!(
+            "// #line 2 \"{}\"",
+            dir.join("main.wgsl").display()
+        )));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn transitive_include() {
+        let dir = write_files(
+            "transitive",
+            &[
+                ("main.wgsl", "// #include \"lib/a.wgsl\"\nfn main() {}"),
+                ("lib/a.wgsl", "  // #include \"b.wgsl\"\nfn a() {}"),
+                ("lib/b.wgsl", "fn b() {}"),
+            ],
+        );
+        let output = preprocess_wgsl(&dir.join("main.wgsl")).unwrap();
+        assert_eq!(code(&output), ["fn b() {}", "fn a() {}", "fn main() {}"]);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn duplicate_include() {
+        let dir = write_files(
+            "duplicate",
+            &[
+                (
+                    "main.wgsl",
+                    "// #include \"a.wgsl\"\n// #include \"common.wgsl\"\nfn main() {}",
+                ),
+                ("a.wgsl", "// #include \"common.wgsl\"\nfn a() {}"),
+                ("common.wgsl", "fn common() {}"),
+            ],
+        );
+        let output = preprocess_wgsl(&dir.join("main.wgsl")).unwrap();
+        assert_eq!(
+            code(&output),
+            ["fn common() {}", "fn a() {}", "fn main() {}"]
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn include_cycle() {
+        let dir = write_files(
+            "cycle",
+            &[
+                ("a.wgsl", "// #include \"b.wgsl\""),
+                ("b.wgsl", "// #include \"a.wgsl\""),
+            ],
+        );
+        let err = preprocess_wgsl(&dir.join("a.wgsl")).unwrap_err();
+        assert_eq!(
+            err,
+            PreprocessError::IncludeCycle(vec![
+                dir.join("a.wgsl"),
+                dir.join("b.wgsl"),
+                dir.join("a.wgsl"),
+            ])
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn malformed_include() {
+        let dir = write_files("malformed", &[("main.wgsl", "\n// #include common.wgsl")]);
+        let err = preprocess_wgsl(&dir.join("main.wgsl")).unwrap_err();
+        assert_eq!(
+            err,
+            PreprocessError::MalformedInclude {
+                path: dir.join("main.wgsl"),
+                line: 2,
+            }
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn source_map_segments() {
+        let mut shader = ShaderWithSourceMap::new();
+        shader.push("prelude.wgsl", "let a: f32 = 1.0;\nlet b: f32 = 2.0;\n");
+        shader.push("user.wgsl", "fn main() {\n    let c = a + b;\n}");
+        assert_eq!(shader.source().lines().count(), 5);
+        assert_eq!(shader.map_error_line(2), ("prelude.wgsl".to_owned(), 2));
+        assert_eq!(shader.map_error_line(3), ("user.wgsl".to_owned(), 1));
+        assert_eq!(shader.map_error_line(4), ("user.wgsl".to_owned(), 2));
+        assert_eq!(
+            ShaderWithSourceMap::new().map_error_line(7),
+            (String::new(), 7)
+        );
+    }
+
+    #[test]
+    fn source_map_from_preprocessed() {
+        let dir = write_files(
+            "source-map",
+            &[
+                ("main.wgsl", "// #include \"common.wgsl\"\nfn main() {}"),
+                ("common.wgsl", "fn common() {}\nfn other() {}"),
+            ],
+        );
+        let output = preprocess_wgsl(&dir.join("main.wgsl")).unwrap();
+        let shader = ShaderWithSourceMap::from_preprocessed(&output);
+        assert_eq!(shader.source(), output);
+
+        let line = output
+            .lines()
+            .position(|line| line == "fn other() {}")
+            .unwrap() as u32
+            + 1;
+        let common = dir.join("common.wgsl").display().to_string();
+        assert_eq!(shader.map_error_line(line), (common, 2));
+        let line = output
+            .lines()
+            .position(|line| line == "fn main() {}")
+            .unwrap() as u32
+            + 1;
+        let main = dir.join("main.wgsl").display().to_string();
+        assert_eq!(shader.map_error_line(line), (main, 2));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
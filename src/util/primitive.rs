@@ -0,0 +1,79 @@
+use wgt::{Face, FrontFace, PolygonMode, PrimitiveState, PrimitiveTopology};
+
+/// Presets for the common [`PrimitiveState`] setups.
+///
+/// None of them clamp depth or rasterize conservatively.
+pub trait PrimitiveStateExt: Sized {
+    /// Filled triangle list, with counter-clockwise front faces and back faces culled.
+    fn triangles() -> Self;
+
+    /// Line list, without culling.
+    fn lines() -> Self;
+
+    /// Triangle list drawn as the outlines of its triangles, without culling.
+    ///
+    /// Requires [`Features::NON_FILL_POLYGON_MODE`](wgt::Features::NON_FILL_POLYGON_MODE).
+    fn wireframe() -> Self;
+}
+
+impl PrimitiveStateExt for PrimitiveState {
+    fn triangles() -> Self {
+        Self {
+            topology: PrimitiveTopology::TriangleList,
+            front_face: FrontFace::Ccw,
+            cull_mode: Some(Face::Back),
+            ..Self::default()
+        }
+    }
+
+    fn lines() -> Self {
+        Self {
+            topology: PrimitiveTopology::LineList,
+            cull_mode: None,
+            ..Self::default()
+        }
+    }
+
+    fn wireframe() -> Self {
+        Self {
+            topology: PrimitiveTopology::TriangleList,
+            cull_mode: None,
+            polygon_mode: PolygonMode::Line,
+            ..Self::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrimitiveStateExt;
+    use wgt::{Face, FrontFace, PolygonMode, PrimitiveState, PrimitiveTopology};
+
+    #[test]
+    fn triangles_preset() {
+        let state = PrimitiveState::triangles();
+        assert_eq!(state.topology, PrimitiveTopology::TriangleList);
+        assert_eq!(state.front_face, FrontFace::Ccw);
+        assert_eq!(state.cull_mode, Some(Face::Back));
+        assert_eq!(state.polygon_mode, PolygonMode::Fill);
+    }
+
+    #[test]
+    fn lines_preset() {
+        let state = PrimitiveState::lines();
+        assert_eq!(state.topology, PrimitiveTopology::LineList);
+        assert_eq!(state.front_face, FrontFace::Ccw);
+        assert_eq!(state.cull_mode, None);
+        assert_eq!(state.polygon_mode, PolygonMode::Fill);
+    }
+
+    #[test]
+    fn wireframe_preset() {
+        let state = PrimitiveState::wireframe();
+        assert_eq!(state.topology, PrimitiveTopology::TriangleList);
+        assert_eq!(state.front_face, FrontFace::Ccw);
+        assert_eq!(state.cull_mode, None);
+        assert_eq!(state.polygon_mode, PolygonMode::Line);
+        assert!(!state.clamp_depth && !state.conservative);
+    }
+}
@@ -0,0 +1,74 @@
+use std::mem::size_of;
+
+use super::RenderEncoder;
+
+/// Bytes of `value` for a push constant write at `offset`.
+///
+/// # Panics
+///
+/// - `offset` or the size of `T` is not a multiple of
+///   [`PUSH_CONSTANT_ALIGNMENT`](crate::PUSH_CONSTANT_ALIGNMENT).
+fn push_constant_bytes<T: bytemuck::Pod>(offset: u32, value: &T) -> &[u8] {
+    assert_eq!(
+        offset % crate::PUSH_CONSTANT_ALIGNMENT,
+        0,
+        "Push constant offset {} is not a multiple of {}",
+        offset,
+        crate::PUSH_CONSTANT_ALIGNMENT
+    );
+    assert_eq!(
+        size_of::<T>() as u32 % crate::PUSH_CONSTANT_ALIGNMENT,
+        0,
+        "Push constant type size {} is not a multiple of {}",
+        size_of::<T>(),
+        crate::PUSH_CONSTANT_ALIGNMENT
+    );
+    bytemuck::bytes_of(value)
+}
+
+/// Writes `value` to the push constants at `offset`, for the given `stages`.
+///
+/// Works with both render passes and render bundle encoders.
+/// [`Features::PUSH_CONSTANTS`](crate::Features::PUSH_CONSTANTS) must be enabled, and the
+/// pipeline layout needs matching ranges, see
+/// [push_constant_ranges](crate::push_constant_ranges).
+///
+/// # Panics
+///
+/// - `offset` or the size of `T` is not a multiple of
+///   [`PUSH_CONSTANT_ALIGNMENT`](crate::PUSH_CONSTANT_ALIGNMENT).
+pub fn push_constants<'a, E: RenderEncoder<'a>, T: bytemuck::Pod>(
+    encoder: &mut E,
+    stages: crate::ShaderStage,
+    offset: u32,
+    value: &T,
+) {
+    encoder.set_push_constants(stages, offset, push_constant_bytes(offset, value));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::push_constant_bytes;
+
+    #[repr(C)]
+    #[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+    struct Locals {
+        color: [f32; 4],
+        index: u32,
+    }
+
+    #[test]
+    fn push_constant_bytes_size() {
+        let locals = Locals {
+            color: [1.0; 4],
+            index: 7,
+        };
+        assert_eq!(push_constant_bytes(16, &locals).len(), 20);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a multiple of 4")]
+    fn push_constant_bytes_misaligned_offset() {
+        push_constant_bytes(2, &0u32);
+    }
+}
@@ -0,0 +1,99 @@
+use std::{error, fmt::Display, future::Future};
+
+use super::{image_data_layout, read_buffer};
+
+/// Error returned by [`read_texture_to_image`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ReadTextureError {
+    /// Only 8-bit RGBA and BGRA formats can be converted to an [`RgbaImage`](image::RgbaImage).
+    UnsupportedFormat(crate::TextureFormat),
+    /// Mapping the readback buffer failed.
+    Map(crate::BufferAsyncError),
+}
+
+impl Display for ReadTextureError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::UnsupportedFormat(format) => write!(
+                f,
+                "Texture format {:?} can't be read back as an RGBA image",
+                format
+            ),
+            Self::Map(ref err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for ReadTextureError {}
+
+/// Read back the top mip level of a 2D texture as an [`RgbaImage`](image::RgbaImage).
+///
+/// `format` has to be the format of the texture, one of `Rgba8Unorm`, `Rgba8UnormSrgb`,
+/// `Bgra8Unorm` or `Bgra8UnormSrgb`. The texture needs the
+/// [`COPY_SRC`](crate::TextureUsage::COPY_SRC) usage.
+///
+/// The copy is submitted to `queue` right away, and the device is polled while waiting
+/// for the readback.
+pub fn read_texture_to_image<'a>(
+    device: &'a crate::Device,
+    queue: &crate::Queue,
+    texture: &crate::Texture,
+    format: crate::TextureFormat,
+    size: crate::Extent3d,
+) -> impl Future<Output = Result<image::RgbaImage, ReadTextureError>> + Send + 'a {
+    let swap_red_blue = match format {
+        crate::TextureFormat::Rgba8Unorm | crate::TextureFormat::Rgba8UnormSrgb => Ok(false),
+        crate::TextureFormat::Bgra8Unorm | crate::TextureFormat::Bgra8UnormSrgb => Ok(true),
+        _ => Err(ReadTextureError::UnsupportedFormat(format)),
+    };
+    let (layout, padded_bytes_per_row) = image_data_layout(format, size.width, size.height);
+
+    let download = swap_red_blue.as_ref().ok().map(|_| {
+        let download = device.create_buffer(&crate::BufferDescriptor {
+            label: Some("texture readback"),
+            size: padded_bytes_per_row as crate::BufferAddress
+                * size.height as crate::BufferAddress,
+            usage: crate::BufferUsage::COPY_DST | crate::BufferUsage::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder =
+            device.create_command_encoder(&crate::CommandEncoderDescriptor { label: None });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            crate::ImageCopyBuffer {
+                buffer: &download,
+                layout,
+            },
+            crate::Extent3d {
+                depth_or_array_layers: 1,
+                ..size
+            },
+        );
+        queue.submit(Some(encoder.finish()));
+        download
+    });
+
+    async move {
+        let swap_red_blue = swap_red_blue?;
+        let download = download.unwrap();
+        let data = read_buffer(device, &download, ..)
+            .await
+            .map_err(ReadTextureError::Map)?;
+
+        // Strip the row padding required by the copy
+        let unpadded_bytes_per_row = size.width as usize * 4;
+        let mut pixels = Vec::with_capacity(unpadded_bytes_per_row * size.height as usize);
+        for row in data.chunks(padded_bytes_per_row as usize) {
+            pixels.extend_from_slice(&row[..unpadded_bytes_per_row]);
+        }
+        if swap_red_blue {
+            for pixel in pixels.chunks_mut(4) {
+                pixel.swap(0, 2);
+            }
+        }
+
+        Ok(image::RgbaImage::from_raw(size.width, size.height, pixels)
+            .expect("readback size mismatch"))
+    }
+}
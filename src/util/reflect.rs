@@ -0,0 +1,247 @@
+use naga::{
+    valid::{Capabilities, ValidationFlags, Validator},
+    ImageClass, ImageDimension, ScalarKind, StorageAccess, StorageClass, StorageFormat, TypeInner,
+};
+
+use crate::{
+    BindGroupLayoutEntry, BindingType, BufferBindingType, ShaderStage, StorageTextureAccess,
+    TextureFormat, TextureSampleType, TextureViewDimension,
+};
+
+/// Derives the bind group layout entries of every group used by a naga module.
+///
+/// The returned vector is indexed by group, and each group's entries are sorted by binding.
+/// The visibility of an entry is the set of stages whose entry points use it, which is empty
+/// if no entry point does.
+///
+/// Whether a sampler is filtering, or a float texture filterable, can't be told from the
+/// shader alone. Everything is assumed filtering, except the `(group, binding)` pairs listed
+/// in `non_filtering`.
+///
+/// Returns an error if the module fails validation.
+pub fn reflect_bind_group_layouts(
+    module: &naga::Module,
+    non_filtering: &[(u32, u32)],
+) -> Result<Vec<Vec<BindGroupLayoutEntry>>, String> {
+    let info = Validator::new(ValidationFlags::all(), Capabilities::all())
+        .validate(module)
+        .map_err(|err| err.to_string())?;
+
+    let mut groups = Vec::<Vec<BindGroupLayoutEntry>>::new();
+    for (handle, var) in module.global_variables.iter() {
+        let binding = match var.binding {
+            Some(ref binding) => binding,
+            None => continue,
+        };
+        let filtering = !non_filtering.contains(&(binding.group, binding.binding));
+        let ty = match reflect_binding_type(module, var, filtering) {
+            Some(ty) => ty,
+            None => continue,
+        };
+
+        let mut visibility = ShaderStage::NONE;
+        for (index, entry_point) in module.entry_points.iter().enumerate() {
+            if !info.get_entry_point(index)[handle].is_empty() {
+                visibility |= match entry_point.stage {
+                    naga::ShaderStage::Vertex => ShaderStage::VERTEX,
+                    naga::ShaderStage::Fragment => ShaderStage::FRAGMENT,
+                    naga::ShaderStage::Compute => ShaderStage::COMPUTE,
+                };
+            }
+        }
+
+        let group = binding.group as usize;
+        if groups.len() <= group {
+            groups.resize_with(group + 1, Vec::new);
+        }
+        groups[group].push(BindGroupLayoutEntry {
+            binding: binding.binding,
+            visibility,
+            ty,
+            count: None,
+        });
+    }
+
+    for entries in groups.iter_mut() {
+        entries.sort_by_key(|entry| entry.binding);
+    }
+    Ok(groups)
+}
+
+fn reflect_binding_type(
+    module: &naga::Module,
+    var: &naga::GlobalVariable,
+    filtering: bool,
+) -> Option<BindingType> {
+    Some(match var.class {
+        StorageClass::Uniform => BindingType::Buffer {
+            ty: BufferBindingType::Uniform,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        StorageClass::Storage => BindingType::Buffer {
+            ty: BufferBindingType::Storage {
+                read_only: !var.storage_access.contains(StorageAccess::STORE),
+            },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        StorageClass::Handle => match module.types[var.ty].inner {
+            TypeInner::Sampler { comparison } => BindingType::Sampler {
+                filtering,
+                comparison,
+            },
+            TypeInner::Image {
+                dim,
+                arrayed,
+                class,
+            } => {
+                let view_dimension = match (dim, arrayed) {
+                    (ImageDimension::D1, _) => TextureViewDimension::D1,
+                    (ImageDimension::D2, false) => TextureViewDimension::D2,
+                    (ImageDimension::D2, true) => TextureViewDimension::D2Array,
+                    (ImageDimension::D3, _) => TextureViewDimension::D3,
+                    (ImageDimension::Cube, false) => TextureViewDimension::Cube,
+                    (ImageDimension::Cube, true) => TextureViewDimension::CubeArray,
+                };
+                match class {
+                    ImageClass::Sampled { kind, multi } => BindingType::Texture {
+                        multisampled: multi,
+                        sample_type: match kind {
+                            ScalarKind::Float => TextureSampleType::Float {
+                                filterable: filtering,
+                            },
+                            ScalarKind::Sint => TextureSampleType::Sint,
+                            ScalarKind::Uint => TextureSampleType::Uint,
+                            ScalarKind::Bool => return None,
+                        },
+                        view_dimension,
+                    },
+                    ImageClass::Depth => BindingType::Texture {
+                        multisampled: false,
+                        sample_type: TextureSampleType::Depth,
+                        view_dimension,
+                    },
+                    ImageClass::Storage(format) => BindingType::StorageTexture {
+                        access: if !var.storage_access.contains(StorageAccess::STORE) {
+                            StorageTextureAccess::ReadOnly
+                        } else if !var.storage_access.contains(StorageAccess::LOAD) {
+                            StorageTextureAccess::WriteOnly
+                        } else {
+                            StorageTextureAccess::ReadWrite
+                        },
+                        format: map_storage_format(format),
+                        view_dimension,
+                    },
+                }
+            }
+            _ => return None,
+        },
+        _ => return None,
+    })
+}
+
+fn map_storage_format(format: StorageFormat) -> TextureFormat {
+    match format {
+        StorageFormat::R8Unorm => TextureFormat::R8Unorm,
+        StorageFormat::R8Snorm => TextureFormat::R8Snorm,
+        StorageFormat::R8Uint => TextureFormat::R8Uint,
+        StorageFormat::R8Sint => TextureFormat::R8Sint,
+        StorageFormat::R16Uint => TextureFormat::R16Uint,
+        StorageFormat::R16Sint => TextureFormat::R16Sint,
+        StorageFormat::R16Float => TextureFormat::R16Float,
+        StorageFormat::Rg8Unorm => TextureFormat::Rg8Unorm,
+        StorageFormat::Rg8Snorm => TextureFormat::Rg8Snorm,
+        StorageFormat::Rg8Uint => TextureFormat::Rg8Uint,
+        StorageFormat::Rg8Sint => TextureFormat::Rg8Sint,
+        StorageFormat::R32Uint => TextureFormat::R32Uint,
+        StorageFormat::R32Sint => TextureFormat::R32Sint,
+        StorageFormat::R32Float => TextureFormat::R32Float,
+        StorageFormat::Rg16Uint => TextureFormat::Rg16Uint,
+        StorageFormat::Rg16Sint => TextureFormat::Rg16Sint,
+        StorageFormat::Rg16Float => TextureFormat::Rg16Float,
+        StorageFormat::Rgba8Unorm => TextureFormat::Rgba8Unorm,
+        StorageFormat::Rgba8Snorm => TextureFormat::Rgba8Snorm,
+        StorageFormat::Rgba8Uint => TextureFormat::Rgba8Uint,
+        StorageFormat::Rgba8Sint => TextureFormat::Rgba8Sint,
+        StorageFormat::Rgb10a2Unorm => TextureFormat::Rgb10a2Unorm,
+        StorageFormat::Rg11b10Float => TextureFormat::Rg11b10Float,
+        StorageFormat::Rg32Uint => TextureFormat::Rg32Uint,
+        StorageFormat::Rg32Sint => TextureFormat::Rg32Sint,
+        StorageFormat::Rg32Float => TextureFormat::Rg32Float,
+        StorageFormat::Rgba16Uint => TextureFormat::Rgba16Uint,
+        StorageFormat::Rgba16Sint => TextureFormat::Rgba16Sint,
+        StorageFormat::Rgba16Float => TextureFormat::Rgba16Float,
+        StorageFormat::Rgba32Uint => TextureFormat::Rgba32Uint,
+        StorageFormat::Rgba32Sint => TextureFormat::Rgba32Sint,
+        StorageFormat::Rgba32Float => TextureFormat::Rgba32Float,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BindingType, BufferBindingType, ShaderStage, TextureSampleType};
+
+    const SHADER: &str = r#"
+[[block]]
+struct Locals {
+    transform: mat4x4<f32>;
+};
+[[group(0), binding(0)]]
+var<uniform> r_locals: Locals;
+
+[[stage(vertex)]]
+fn vs_main([[location(0)]] position: vec4<f32>) -> [[builtin(position)]] vec4<f32> {
+    return r_locals.transform * position;
+}
+
+[[group(1), binding(1)]]
+var r_sampler: sampler;
+[[group(1), binding(0)]]
+var r_color: texture_2d<f32>;
+
+[[stage(fragment)]]
+fn fs_main() -> [[location(0)]] vec4<f32> {
+    return textureSample(r_color, r_sampler, vec2<f32>(0.5, 0.5));
+}
+"#;
+
+    #[test]
+    fn reflect_uniform_texture_sampler() {
+        let module = naga::front::wgsl::parse_str(SHADER).unwrap();
+        let groups = super::reflect_bind_group_layouts(&module, &[(1, 1)]).unwrap();
+        assert_eq!(groups.len(), 2);
+
+        assert_eq!(groups[0].len(), 1);
+        assert_eq!(groups[0][0].binding, 0);
+        assert_eq!(groups[0][0].visibility, ShaderStage::VERTEX);
+        assert_eq!(
+            groups[0][0].ty,
+            BindingType::Buffer {
+                ty: BufferBindingType::Uniform,
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            }
+        );
+
+        assert_eq!(groups[1].len(), 2);
+        assert_eq!(groups[1][0].binding, 0);
+        assert_eq!(groups[1][0].visibility, ShaderStage::FRAGMENT);
+        assert_eq!(
+            groups[1][0].ty,
+            BindingType::Texture {
+                multisampled: false,
+                sample_type: TextureSampleType::Float { filterable: true },
+                view_dimension: crate::TextureViewDimension::D2,
+            }
+        );
+        assert_eq!(groups[1][1].binding, 1);
+        assert_eq!(
+            groups[1][1].ty,
+            BindingType::Sampler {
+                filtering: false,
+                comparison: false,
+            }
+        );
+    }
+}
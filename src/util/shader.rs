@@ -0,0 +1,127 @@
+use std::{borrow::Cow, error, fmt::Display, fs, io, path::Path};
+
+/// Error returned by [`DeviceExt::create_shader_module_from_path`](super::DeviceExt::create_shader_module_from_path).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum ShaderLoadError {
+    /// The file could not be read.
+    Io(io::ErrorKind),
+    /// The file extension is neither `wgsl` nor `spv`.
+    UnknownExtension,
+    /// The file is not a SPIR-V binary: its size is not a multiple of 4 or it lacks
+    /// the magic number.
+    InvalidSpirV,
+    /// The WGSL source failed to parse or validate, as described by the message.
+    Validation(String),
+}
+
+impl Display for ShaderLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match *self {
+            Self::Io(kind) => write!(f, "Failed to read shader: {:?}", kind),
+            Self::UnknownExtension => {
+                write!(f, "Shader files must have the `wgsl` or `spv` extension")
+            }
+            Self::InvalidSpirV => write!(f, "Shader file is not a SPIR-V binary"),
+            Self::Validation(ref message) => write!(f, "Invalid shader: {}", message),
+        }
+    }
+}
+
+impl error::Error for ShaderLoadError {}
+
+/// Reads the shader source at `path`, telling WGSL and SPIR-V apart by the extension.
+///
+/// WGSL sources are validated when the `naga` feature is enabled.
+pub(crate) fn load_shader_source(
+    path: &Path,
+) -> Result<crate::ShaderSource<'static>, ShaderLoadError> {
+    const SPIRV_MAGIC_NUMBER: u32 = 0x0723_0203;
+
+    let io_error = |err: io::Error| ShaderLoadError::Io(err.kind());
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("wgsl") => {
+            let source = fs::read_to_string(path).map_err(io_error)?;
+            #[cfg(feature = "naga")]
+            super::validate_wgsl(&source).map_err(ShaderLoadError::Validation)?;
+            Ok(crate::ShaderSource::Wgsl(Cow::Owned(source)))
+        }
+        Some("spv") => {
+            let data = fs::read(path).map_err(io_error)?;
+            if data.len() < 4
+                || data.len() % 4 != 0
+                || u32::from_ne_bytes([data[0], data[1], data[2], data[3]]) != SPIRV_MAGIC_NUMBER
+            {
+                return Err(ShaderLoadError::InvalidSpirV);
+            }
+            Ok(crate::ShaderSource::SpirV(Cow::Owned(
+                super::make_spirv_raw(&data).into_owned(),
+            )))
+        }
+        _ => Err(ShaderLoadError::UnknownExtension),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{load_shader_source, ShaderLoadError};
+    use std::{fs, io, path::Path};
+
+    fn load_temp(
+        name: &str,
+        contents: &[u8],
+    ) -> Result<crate::ShaderSource<'static>, ShaderLoadError> {
+        let dir = std::env::temp_dir().join(format!("wgpu-shader-load-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, contents).unwrap();
+        let result = load_shader_source(&path);
+        fs::remove_file(&path).unwrap();
+        result
+    }
+
+    #[test]
+    fn load_wgsl() {
+        let source = load_temp("valid.wgsl", include_bytes!("fullscreen.wgsl")).unwrap();
+        assert!(matches!(source, crate::ShaderSource::Wgsl(_)));
+    }
+
+    #[cfg(feature = "naga")]
+    #[test]
+    fn load_broken_wgsl() {
+        match load_temp("broken.wgsl", b"[[stage(vertex)]]\nfn 42() {}\n") {
+            // The message points at the offending token
+            Err(ShaderLoadError::Validation(message)) => {
+                assert!(message.contains("'42'"), "unexpected message {:?}", message)
+            }
+            Err(err) => panic!("expected a validation error, got {:?}", err),
+            Ok(_) => panic!("broken WGSL was loaded"),
+        }
+    }
+
+    #[test]
+    fn load_spirv() {
+        let source = load_temp(
+            "valid.spv",
+            include_bytes!("../../examples/texture-arrays/shader.vert.spv"),
+        )
+        .unwrap();
+        assert!(matches!(source, crate::ShaderSource::SpirV(_)));
+
+        assert_eq!(
+            load_temp("broken.spv", b"not spirv").err(),
+            Some(ShaderLoadError::InvalidSpirV)
+        );
+    }
+
+    #[test]
+    fn load_errors() {
+        assert_eq!(
+            load_temp("shader.frag", b"").err(),
+            Some(ShaderLoadError::UnknownExtension)
+        );
+        assert_eq!(
+            load_shader_source(Path::new("does/not/exist.wgsl")).err(),
+            Some(ShaderLoadError::Io(io::ErrorKind::NotFound))
+        );
+    }
+}
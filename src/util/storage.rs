@@ -0,0 +1,112 @@
+use std::{future::Future, marker::PhantomData, mem::size_of};
+
+use super::read_buffer;
+
+/// Storage buffer of `len` values of type `T`, written by shaders and read back to the CPU.
+///
+/// The readback buffer is only created the first time
+/// [`copy_to_readback`](StorageBuffer::copy_to_readback) is called:
+///
+/// ```ignore
+/// let output = wgpu::util::StorageBuffer::<u32>::new(&device, Some("output"), 64);
+/// // Bind `output.binding()` and dispatch the compute shader writing to it
+/// output.copy_to_readback(&device, &mut encoder);
+/// queue.submit(Some(encoder.finish()));
+/// let values = output.read(&device).await?;
+/// ```
+#[derive(Debug)]
+pub struct StorageBuffer<T> {
+    buffer: crate::Buffer,
+    readback: Option<crate::Buffer>,
+    len: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T: bytemuck::Pod> StorageBuffer<T> {
+    /// Creates a storage buffer with room for `len` values.
+    pub fn new(device: &crate::Device, label: crate::Label, len: usize) -> Self {
+        let buffer = device.create_buffer(&crate::BufferDescriptor {
+            label,
+            size: Self::byte_size(len),
+            usage: crate::BufferUsage::STORAGE | crate::BufferUsage::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        Self {
+            buffer,
+            readback: None,
+            len,
+            _marker: PhantomData,
+        }
+    }
+
+    fn byte_size(len: usize) -> crate::BufferAddress {
+        (len * size_of::<T>()) as crate::BufferAddress
+    }
+
+    /// Number of values in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if the buffer holds no values.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The underlying storage buffer.
+    pub fn buffer(&self) -> &crate::Buffer {
+        &self.buffer
+    }
+
+    /// Binding of the whole buffer, for a [`BindGroupEntry`](crate::BindGroupEntry).
+    pub fn binding(&self) -> crate::BindingResource {
+        self.buffer.as_entire_binding()
+    }
+
+    /// Records copying the storage buffer into the readback buffer, creating it if needed.
+    ///
+    /// The copy has to be submitted before calling [`read`](StorageBuffer::read).
+    pub fn copy_to_readback(
+        &mut self,
+        device: &crate::Device,
+        encoder: &mut crate::CommandEncoder,
+    ) {
+        let size = Self::byte_size(self.len);
+        let readback = self.readback.get_or_insert_with(|| {
+            device.create_buffer(&crate::BufferDescriptor {
+                label: Some("storage readback"),
+                size,
+                usage: crate::BufferUsage::COPY_DST | crate::BufferUsage::MAP_READ,
+                mapped_at_creation: false,
+            })
+        });
+        encoder.copy_buffer_to_buffer(&self.buffer, 0, readback, 0, size);
+    }
+
+    /// Reads back the values copied by the last submitted
+    /// [`copy_to_readback`](StorageBuffer::copy_to_readback).
+    ///
+    /// The device is polled while waiting, so no external poller needs to be running.
+    ///
+    /// # Panics
+    ///
+    /// - [`copy_to_readback`](StorageBuffer::copy_to_readback) was never called.
+    pub fn read<'a>(
+        &'a self,
+        device: &crate::Device,
+    ) -> impl Future<Output = Result<Vec<T>, crate::BufferAsyncError>> + Send + 'a {
+        let readback = self
+            .readback
+            .as_ref()
+            .expect("Storage buffer was never copied to its readback buffer");
+        let data = read_buffer(device, readback, ..);
+        let len = self.len;
+        async move {
+            let data = data.await?;
+            // The downloaded bytes aren't necessarily aligned for `T`
+            let mut values = vec![T::zeroed(); len];
+            bytemuck::cast_slice_mut(&mut values).copy_from_slice(&data);
+            Ok(values)
+        }
+    }
+}
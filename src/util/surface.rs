@@ -0,0 +1,220 @@
+use wgt::{PresentMode, SwapChainDescriptor, TextureFormat, TextureUsage};
+
+/// What is known about the capabilities of a surface, as returned by
+/// [`Surface::capabilities_snapshot`](crate::Surface::capabilities_snapshot).
+///
+/// Capabilities that can't be queried are `None`, rather than guessed. Alpha modes are not
+/// part of the snapshot, as swap chains don't have any yet.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SurfaceCapabilitiesSnapshot {
+    /// Swap chain formats known to be supported, the preferred one first. Other formats may
+    /// be supported as well.
+    pub formats: Vec<TextureFormat>,
+    /// Supported present modes, if known.
+    pub present_modes: Option<Vec<PresentMode>>,
+    /// Supported swap chain texture usages, if known.
+    pub usages: Option<TextureUsage>,
+}
+
+impl SurfaceCapabilitiesSnapshot {
+    /// Picks the first sRGB format, falling back to the first format.
+    ///
+    /// See [`select_surface_format`].
+    pub fn best_srgb_format(&self) -> Option<TextureFormat> {
+        select_surface_format(&self.formats)
+    }
+
+    /// Whether the present mode is supported, or `None` if the present modes are unknown.
+    ///
+    /// [`PresentMode::Fifo`] is always supported.
+    pub fn supports(&self, present_mode: PresentMode) -> Option<bool> {
+        if present_mode == PresentMode::Fifo {
+            return Some(true);
+        }
+        self.present_modes
+            .as_ref()
+            .map(|present_modes| present_modes.contains(&present_mode))
+    }
+
+    /// Substitutes the unsupported values of `requested` with supported ones, logging each
+    /// substitution.
+    ///
+    /// An unsupported format is replaced with [`best_srgb_format`](Self::best_srgb_format),
+    /// an unsupported present mode with [`PresentMode::Fifo`], and unsupported usages are
+    /// dropped. Values are only substituted when the matching capability is known, so the
+    /// format is kept if no format is known at all, and so are the present mode and usages
+    /// while they are `None`.
+    pub fn resolve(&self, requested: &SwapChainDescriptor) -> SwapChainDescriptor {
+        let mut resolved = requested.clone();
+        if !self.formats.contains(&requested.format) {
+            if let Some(format) = self.best_srgb_format() {
+                log::warn!(
+                    "Swap chain format {:?} is not supported, using {:?}",
+                    requested.format,
+                    format
+                );
+                resolved.format = format;
+            }
+        }
+        if self.supports(requested.present_mode) == Some(false) {
+            resolved.present_mode = PresentMode::Fifo;
+            log::warn!(
+                "Present mode {:?} is not supported, using {:?}",
+                requested.present_mode,
+                resolved.present_mode
+            );
+        }
+        if let Some(usages) = self.usages {
+            if !usages.contains(requested.usage) {
+                resolved.usage = requested.usage & usages;
+                log::warn!(
+                    "Swap chain usages {:?} are not supported, using {:?}",
+                    requested.usage - usages,
+                    resolved.usage
+                );
+            }
+        }
+        resolved
+    }
+}
+
+/// Picks the first of the `preferred` present modes found in `supported`.
+///
+/// Falls back to [`PresentMode::Fifo`], which every surface supports.
+pub fn select_present_mode(supported: &[PresentMode], preferred: &[PresentMode]) -> PresentMode {
+    preferred
+        .iter()
+        .copied()
+        .find(|mode| supported.contains(mode))
+        .unwrap_or(PresentMode::Fifo)
+}
+
+/// Picks the first sRGB format of `supported`, falling back to the first format.
+///
+/// Returns `None` if `supported` is empty.
+pub fn select_surface_format(supported: &[TextureFormat]) -> Option<TextureFormat> {
+    supported
+        .iter()
+        .copied()
+        .find(|&format| {
+            format == TextureFormat::Bgra8UnormSrgb || format == TextureFormat::Rgba8UnormSrgb
+        })
+        .or_else(|| supported.first().copied())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select_present_mode, select_surface_format, SurfaceCapabilitiesSnapshot};
+    use wgt::{PresentMode, SwapChainDescriptor, TextureFormat, TextureUsage};
+
+    #[test]
+    fn present_mode_preference_order() {
+        let supported = [
+            PresentMode::Fifo,
+            PresentMode::Immediate,
+            PresentMode::Mailbox,
+        ];
+        assert_eq!(
+            select_present_mode(&supported, &[PresentMode::Mailbox, PresentMode::Immediate]),
+            PresentMode::Mailbox
+        );
+        assert_eq!(
+            select_present_mode(
+                &supported[..2],
+                &[PresentMode::Mailbox, PresentMode::Immediate]
+            ),
+            PresentMode::Immediate
+        );
+    }
+
+    #[test]
+    fn present_mode_fifo_fallback() {
+        assert_eq!(
+            select_present_mode(&[PresentMode::Fifo], &[PresentMode::Mailbox]),
+            PresentMode::Fifo
+        );
+        assert_eq!(select_present_mode(&[], &[]), PresentMode::Fifo);
+    }
+
+    #[test]
+    fn surface_format_prefers_srgb() {
+        assert_eq!(
+            select_surface_format(&[TextureFormat::Bgra8Unorm, TextureFormat::Bgra8UnormSrgb]),
+            Some(TextureFormat::Bgra8UnormSrgb)
+        );
+        assert_eq!(
+            select_surface_format(&[TextureFormat::Rgba16Float, TextureFormat::Bgra8Unorm]),
+            Some(TextureFormat::Rgba16Float)
+        );
+        assert_eq!(select_surface_format(&[]), None);
+    }
+
+    #[test]
+    fn capabilities_snapshot_helpers() {
+        let caps = SurfaceCapabilitiesSnapshot {
+            formats: vec![TextureFormat::Rgba16Float, TextureFormat::Rgba8UnormSrgb],
+            present_modes: Some(vec![PresentMode::Fifo, PresentMode::Mailbox]),
+            usages: Some(TextureUsage::RENDER_ATTACHMENT),
+        };
+        assert_eq!(caps.best_srgb_format(), Some(TextureFormat::Rgba8UnormSrgb));
+        assert_eq!(caps.supports(PresentMode::Mailbox), Some(true));
+        assert_eq!(caps.supports(PresentMode::Immediate), Some(false));
+
+        let caps = SurfaceCapabilitiesSnapshot {
+            formats: vec![TextureFormat::Bgra8Unorm],
+            present_modes: None,
+            ..caps
+        };
+        assert_eq!(caps.best_srgb_format(), Some(TextureFormat::Bgra8Unorm));
+        assert_eq!(caps.supports(PresentMode::Mailbox), None);
+        assert_eq!(caps.supports(PresentMode::Fifo), Some(true));
+    }
+
+    #[test]
+    fn capabilities_resolve_swap_chain() {
+        let caps = SurfaceCapabilitiesSnapshot {
+            formats: vec![TextureFormat::Bgra8UnormSrgb],
+            present_modes: Some(vec![PresentMode::Fifo]),
+            usages: Some(TextureUsage::RENDER_ATTACHMENT),
+        };
+        let supported = SwapChainDescriptor {
+            usage: TextureUsage::RENDER_ATTACHMENT,
+            format: TextureFormat::Bgra8UnormSrgb,
+            width: 640,
+            height: 480,
+            present_mode: PresentMode::Fifo,
+        };
+        assert_eq!(caps.resolve(&supported), supported);
+
+        let requested = SwapChainDescriptor {
+            usage: TextureUsage::RENDER_ATTACHMENT | TextureUsage::COPY_SRC,
+            format: TextureFormat::Rgba16Float,
+            present_mode: PresentMode::Mailbox,
+            ..supported.clone()
+        };
+        assert_eq!(caps.resolve(&requested), supported);
+    }
+
+    #[test]
+    fn capabilities_resolve_keeps_unknown() {
+        let caps = SurfaceCapabilitiesSnapshot {
+            formats: vec![TextureFormat::Bgra8UnormSrgb],
+            present_modes: None,
+            usages: None,
+        };
+        let requested = SwapChainDescriptor {
+            usage: TextureUsage::RENDER_ATTACHMENT | TextureUsage::COPY_SRC,
+            format: TextureFormat::Bgra8Unorm,
+            width: 640,
+            height: 480,
+            present_mode: PresentMode::Mailbox,
+        };
+        assert_eq!(
+            caps.resolve(&requested),
+            SwapChainDescriptor {
+                format: TextureFormat::Bgra8UnormSrgb,
+                ..requested.clone()
+            }
+        );
+    }
+}
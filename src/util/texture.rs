@@ -0,0 +1,510 @@
+use std::num::NonZeroU32;
+
+use wgt::{
+    DownlevelFlags, Extent3d, Features, ImageDataLayout, Origin3d, TextureAspect, TextureDimension,
+    TextureFormat, TextureSampleType, TextureUsage, COPY_BYTES_PER_ROW_ALIGNMENT,
+};
+
+use crate::{Device, Texture, TextureDescriptor, TextureView};
+
+/// Mip chain and copy region helpers for [`Extent3d`].
+///
+/// Unlike [`Extent3d::max_mips`], the mip helpers take the [`TextureDimension`] into account:
+/// for 2D textures `depth_or_array_layers` counts array layers, which are never downsized.
+pub trait Extent3dExt: Sized {
+    /// Size of a 2D texture with a single layer.
+    fn d2(width: u32, height: u32) -> Self;
+
+    /// Component-wise minimum of both sizes, e.g. to clamp a copy to a smaller texture.
+    fn min(&self, other: Extent3d) -> Self;
+
+    /// Whether `origin` lies within a region of this size starting at the zero origin.
+    fn contains_origin(&self, origin: Origin3d) -> bool;
+
+    /// Number of mip levels in the full mip chain of a texture of this size,
+    /// i.e. `floor(log2(max_axis)) + 1`.
+    fn mip_level_count(&self, dimension: TextureDimension) -> u32;
+
+    /// Size of the given mip `level` of a texture of this size.
+    ///
+    /// Each downsized axis is `max(1, size >> level)`.
+    fn mip_level_size(&self, level: u32, dimension: TextureDimension) -> Extent3d;
+}
+
+impl Extent3dExt for Extent3d {
+    fn d2(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        }
+    }
+
+    fn min(&self, other: Extent3d) -> Self {
+        Self {
+            width: self.width.min(other.width),
+            height: self.height.min(other.height),
+            depth_or_array_layers: self.depth_or_array_layers.min(other.depth_or_array_layers),
+        }
+    }
+
+    fn contains_origin(&self, origin: Origin3d) -> bool {
+        origin.x < self.width && origin.y < self.height && origin.z < self.depth_or_array_layers
+    }
+
+    fn mip_level_count(&self, dimension: TextureDimension) -> u32 {
+        let max_axis = match dimension {
+            TextureDimension::D1 => self.width,
+            TextureDimension::D2 => self.width.max(self.height),
+            TextureDimension::D3 => self.width.max(self.height).max(self.depth_or_array_layers),
+        };
+        32 - max_axis.max(1).leading_zeros()
+    }
+
+    fn mip_level_size(&self, level: u32, dimension: TextureDimension) -> Extent3d {
+        let mip = |size: u32| size.checked_shr(level).unwrap_or(0).max(1);
+        match dimension {
+            TextureDimension::D1 => Extent3d {
+                width: mip(self.width),
+                ..*self
+            },
+            TextureDimension::D2 => Extent3d {
+                width: mip(self.width),
+                height: mip(self.height),
+                depth_or_array_layers: self.depth_or_array_layers,
+            },
+            TextureDimension::D3 => Extent3d {
+                width: mip(self.width),
+                height: mip(self.height),
+                depth_or_array_layers: mip(self.depth_or_array_layers),
+            },
+        }
+    }
+}
+
+/// Offset helpers for [`Origin3d`].
+pub trait Origin3dExt {
+    /// Origin moved by the given amount along each axis.
+    fn offset(&self, dx: u32, dy: u32, dz: u32) -> Self;
+}
+
+impl Origin3dExt for Origin3d {
+    fn offset(&self, dx: u32, dy: u32, dz: u32) -> Self {
+        Self {
+            x: self.x + dx,
+            y: self.y + dy,
+            z: self.z + dz,
+        }
+    }
+}
+
+/// Shorthand constructors for [`TextureDescriptor`].
+///
+/// The raw struct remains available for anything these don't cover.
+pub trait TextureDescriptorExt: Sized {
+    /// Describes a plain 2D texture of a single layer, mip level and sample,
+    /// with no label.
+    fn d2(width: u32, height: u32, format: TextureFormat, usage: TextureUsage) -> Self;
+
+    /// Replaces the mip level count.
+    fn with_mips(self, mip_level_count: u32) -> Self;
+}
+
+impl<'a> TextureDescriptorExt for TextureDescriptor<'a> {
+    fn d2(width: u32, height: u32, format: TextureFormat, usage: TextureUsage) -> Self {
+        Self {
+            label: None,
+            size: Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format,
+            usage,
+        }
+    }
+
+    fn with_mips(self, mip_level_count: u32) -> Self {
+        Self {
+            mip_level_count,
+            ..self
+        }
+    }
+}
+
+/// Size and requirement queries for [`TextureFormat`].
+pub trait TextureFormatExt {
+    /// Size in bytes of a texel block, which is a single texel for uncompressed formats.
+    ///
+    /// Depth-stencil formats don't have a size as a whole, so `aspect` has to pick one of
+    /// their aspects. Returns `None` if the aspect doesn't exist in the format, or has no
+    /// defined size, like the depth aspect of `Depth24Plus` formats.
+    fn block_size(&self, aspect: Option<TextureAspect>) -> Option<u32>;
+
+    /// Width and height of a texel block in texels, `(1, 1)` for uncompressed formats.
+    fn block_dimensions(&self) -> (u32, u32);
+
+    /// Features the device needs to create textures of this format, e.g.
+    /// [`TEXTURE_COMPRESSION_BC`](Features::TEXTURE_COMPRESSION_BC) for BCn formats.
+    ///
+    /// Empty for the formats every device supports.
+    fn required_features(&self) -> Features;
+
+    /// Downlevel capabilities the adapter needs to use textures of this format with `usage`.
+    ///
+    /// No format depends on downlevel capabilities on its own, but some usages do, like
+    /// [`STORAGE`](TextureUsage::STORAGE) needing
+    /// [`STORAGE_IMAGES`](DownlevelFlags::STORAGE_IMAGES).
+    fn required_downlevel_flags(&self, usage: TextureUsage) -> DownlevelFlags;
+}
+
+impl TextureFormatExt for TextureFormat {
+    fn block_size(&self, aspect: Option<TextureAspect>) -> Option<u32> {
+        let aspect = aspect.unwrap_or(TextureAspect::All);
+        match *self {
+            TextureFormat::Depth24Plus => None,
+            TextureFormat::Depth24PlusStencil8 => match aspect {
+                TextureAspect::StencilOnly => Some(1),
+                TextureAspect::All | TextureAspect::DepthOnly => None,
+            },
+            TextureFormat::Depth32Float => match aspect {
+                TextureAspect::All | TextureAspect::DepthOnly => Some(4),
+                TextureAspect::StencilOnly => None,
+            },
+            format => match aspect {
+                TextureAspect::All => Some(format.describe().block_size as u32),
+                TextureAspect::DepthOnly | TextureAspect::StencilOnly => None,
+            },
+        }
+    }
+
+    fn block_dimensions(&self) -> (u32, u32) {
+        let (width, height) = self.describe().block_dimensions;
+        (width as u32, height as u32)
+    }
+
+    fn required_features(&self) -> Features {
+        self.describe().required_features
+    }
+
+    fn required_downlevel_flags(&self, usage: TextureUsage) -> DownlevelFlags {
+        if usage.contains(TextureUsage::STORAGE) {
+            DownlevelFlags::STORAGE_IMAGES
+        } else {
+            DownlevelFlags::empty()
+        }
+    }
+}
+
+/// Whether `format` is a depth or depth-stencil format.
+pub fn is_depth_format(format: TextureFormat) -> bool {
+    format.describe().sample_type == TextureSampleType::Depth
+}
+
+/// Creates a 2D depth texture of `format`, usable as a render attachment, along with
+/// its default view.
+///
+/// # Panics
+///
+/// - `format` is not a depth format, see [`is_depth_format`].
+pub fn create_depth_texture(
+    device: &Device,
+    width: u32,
+    height: u32,
+    format: TextureFormat,
+) -> (Texture, TextureView) {
+    let texture = device.create_texture(&depth_texture_descriptor(width, height, format));
+    let view = texture.default_view();
+    (texture, view)
+}
+
+fn depth_texture_descriptor(
+    width: u32,
+    height: u32,
+    format: TextureFormat,
+) -> TextureDescriptor<'static> {
+    assert!(
+        is_depth_format(format),
+        "{:?} is not a depth format",
+        format
+    );
+    TextureDescriptor {
+        label: Some("depth"),
+        ..TextureDescriptor::d2(width, height, format, TextureUsage::RENDER_ATTACHMENT)
+    }
+}
+
+/// Computes the layout of a `width` x `height` image of the given `format` in a buffer,
+/// with rows padded to [`COPY_BYTES_PER_ROW_ALIGNMENT`] as required by buffer-texture copies.
+///
+/// Returns the layout along with the padded bytes per row. For block compressed formats, rows
+/// are rows of blocks, so the buffer needs `padded_bytes_per_row * ceil(height / block_height)`
+/// bytes to hold the image.
+pub fn image_data_layout(format: TextureFormat, width: u32, height: u32) -> (ImageDataLayout, u32) {
+    let info = format.describe();
+    let block_width = info.block_dimensions.0 as u32;
+    let block_height = info.block_dimensions.1 as u32;
+    let width_blocks = (width + block_width - 1) / block_width;
+    let height_blocks = (height + block_height - 1) / block_height;
+
+    let unpadded_bytes_per_row = width_blocks * info.block_size as u32;
+    let align_mask = COPY_BYTES_PER_ROW_ALIGNMENT - 1;
+    let padded_bytes_per_row = (unpadded_bytes_per_row + align_mask) & !align_mask;
+
+    let layout = ImageDataLayout {
+        offset: 0,
+        bytes_per_row: NonZeroU32::new(padded_bytes_per_row),
+        // Rows per image are counted in texels of the physical size.
+        rows_per_image: NonZeroU32::new(height_blocks * block_height),
+    };
+    (layout, padded_bytes_per_row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        depth_texture_descriptor, image_data_layout, is_depth_format, Extent3dExt, Origin3dExt,
+        TextureDescriptorExt, TextureFormatExt,
+    };
+    use crate::TextureDescriptor;
+    use std::num::NonZeroU32;
+    use wgt::{
+        DownlevelFlags, Extent3d, Features, Origin3d, TextureAspect, TextureDimension,
+        TextureFormat, TextureUsage,
+    };
+
+    fn extent(width: u32, height: u32, depth_or_array_layers: u32) -> Extent3d {
+        Extent3d {
+            width,
+            height,
+            depth_or_array_layers,
+        }
+    }
+
+    #[test]
+    fn mips_1x1() {
+        let size = extent(1, 1, 1);
+        assert_eq!(size.mip_level_count(TextureDimension::D2), 1);
+        assert_eq!(size.mip_level_size(0, TextureDimension::D2), size);
+    }
+
+    #[test]
+    fn extent_d2_has_one_layer() {
+        assert_eq!(Extent3d::d2(640, 480), extent(640, 480, 1));
+    }
+
+    #[test]
+    fn extent_min_per_component() {
+        let min = extent(256, 64, 6).min(extent(128, 128, 1));
+        assert_eq!(min, extent(128, 64, 1));
+    }
+
+    #[test]
+    fn extent_contains_origin() {
+        let size = extent(4, 4, 1);
+        let origin = Origin3d { x: 0, y: 0, z: 0 };
+        assert!(size.contains_origin(origin));
+        assert!(size.contains_origin(origin.offset(3, 3, 0)));
+        assert!(!size.contains_origin(origin.offset(4, 3, 0)));
+        assert!(!size.contains_origin(origin.offset(0, 0, 1)));
+        assert_eq!(origin.offset(1, 2, 3), Origin3d { x: 1, y: 2, z: 3 });
+    }
+
+    #[test]
+    fn mips_non_square() {
+        let size = extent(100, 7, 6);
+        assert_eq!(size.mip_level_count(TextureDimension::D2), 7);
+        assert_eq!(
+            size.mip_level_size(1, TextureDimension::D2),
+            extent(50, 3, 6)
+        );
+        assert_eq!(
+            size.mip_level_size(3, TextureDimension::D2),
+            extent(12, 1, 6)
+        );
+    }
+
+    #[test]
+    fn mips_3d() {
+        let size = extent(4, 8, 32);
+        assert_eq!(size.mip_level_count(TextureDimension::D3), 6);
+        // Array layers don't contribute to the 2D mip chain.
+        assert_eq!(size.mip_level_count(TextureDimension::D2), 4);
+        assert_eq!(
+            size.mip_level_size(2, TextureDimension::D3),
+            extent(1, 2, 8)
+        );
+    }
+
+    #[test]
+    fn last_mip_is_1x1() {
+        for &(width, height) in &[(256, 256), (255, 17), (1, 1024), (1000, 3)] {
+            let size = extent(width, height, 1);
+            let count = size.mip_level_count(TextureDimension::D2);
+            assert_eq!(
+                size.mip_level_size(count - 1, TextureDimension::D2),
+                extent(1, 1, 1)
+            );
+            assert_ne!(
+                size.mip_level_size(count - 2, TextureDimension::D2),
+                extent(1, 1, 1)
+            );
+        }
+    }
+
+    #[test]
+    fn texture_descriptor_d2() {
+        let desc =
+            TextureDescriptor::d2(256, 128, TextureFormat::Rgba8Unorm, TextureUsage::SAMPLED);
+        assert_eq!(desc.label, None);
+        assert_eq!(desc.size, extent(256, 128, 1));
+        assert_eq!(desc.mip_level_count, 1);
+        assert_eq!(desc.sample_count, 1);
+        assert_eq!(desc.dimension, TextureDimension::D2);
+        assert_eq!(desc.format, TextureFormat::Rgba8Unorm);
+        assert_eq!(desc.usage, TextureUsage::SAMPLED);
+
+        let desc = desc.with_mips(9);
+        assert_eq!(desc.mip_level_count, 9);
+        assert_eq!(desc.size, extent(256, 128, 1));
+    }
+
+    #[test]
+    fn image_data_layout_rgba8() {
+        let (layout, padded_bytes_per_row) = image_data_layout(TextureFormat::Rgba8Unorm, 100, 30);
+        assert_eq!(padded_bytes_per_row, 512);
+        assert_eq!(layout.offset, 0);
+        assert_eq!(layout.bytes_per_row, NonZeroU32::new(512));
+        assert_eq!(layout.rows_per_image, NonZeroU32::new(30));
+    }
+
+    #[test]
+    fn image_data_layout_compressed() {
+        // 26 texels are 7 blocks of 8 bytes, and the height is rounded up to a whole block.
+        let (layout, padded_bytes_per_row) = image_data_layout(TextureFormat::Bc1RgbaUnorm, 26, 10);
+        assert_eq!(padded_bytes_per_row, 256);
+        assert_eq!(layout.rows_per_image, NonZeroU32::new(12));
+
+        let (_, padded_bytes_per_row) = image_data_layout(TextureFormat::Bc1RgbaUnorm, 256, 4);
+        assert_eq!(padded_bytes_per_row, 512);
+    }
+
+    #[test]
+    fn image_data_layout_single_channel() {
+        let (layout, padded_bytes_per_row) = image_data_layout(TextureFormat::R8Unorm, 256, 1);
+        assert_eq!(padded_bytes_per_row, 256);
+        assert_eq!(layout.rows_per_image, NonZeroU32::new(1));
+
+        let (_, padded_bytes_per_row) = image_data_layout(TextureFormat::R8Unorm, 257, 1);
+        assert_eq!(padded_bytes_per_row, 512);
+    }
+
+    #[test]
+    fn physical_size_rounds_to_blocks() {
+        // `Extent3d::physical_size` comes from wgpu-types, these pin down what the helpers
+        // above rely on.
+        assert_eq!(
+            extent(5, 5, 1).physical_size(TextureFormat::Rgba8Unorm),
+            extent(5, 5, 1)
+        );
+        assert_eq!(
+            extent(5, 5, 1).physical_size(TextureFormat::Bc1RgbaUnorm),
+            extent(8, 8, 1)
+        );
+        assert_eq!(
+            extent(16, 8, 3).physical_size(TextureFormat::Bc7RgbaUnorm),
+            extent(16, 8, 3)
+        );
+    }
+
+    #[test]
+    fn depth_formats() {
+        assert!(is_depth_format(TextureFormat::Depth32Float));
+        assert!(is_depth_format(TextureFormat::Depth24Plus));
+        assert!(is_depth_format(TextureFormat::Depth24PlusStencil8));
+        assert!(!is_depth_format(TextureFormat::R32Float));
+        assert!(!is_depth_format(TextureFormat::Bgra8UnormSrgb));
+    }
+
+    #[test]
+    fn depth_texture() {
+        let desc = depth_texture_descriptor(800, 600, TextureFormat::Depth32Float);
+        assert_eq!(desc.size, extent(800, 600, 1));
+        assert_eq!(desc.format, TextureFormat::Depth32Float);
+        assert_eq!(desc.usage, TextureUsage::RENDER_ATTACHMENT);
+        assert_eq!(desc.mip_level_count, 1);
+        assert_eq!(desc.sample_count, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "not a depth format")]
+    fn depth_texture_panics_for_color_format() {
+        depth_texture_descriptor(800, 600, TextureFormat::Rgba8Unorm);
+    }
+
+    #[test]
+    fn format_block_sizes() {
+        let sizes = [
+            (TextureFormat::Rgba8Unorm, Some(4)),
+            (TextureFormat::Rg16Float, Some(4)),
+            (TextureFormat::Rgba32Float, Some(16)),
+            (TextureFormat::Bc1RgbaUnorm, Some(8)),
+            (TextureFormat::Bc7RgbaUnorm, Some(16)),
+            (TextureFormat::Depth32Float, Some(4)),
+            (TextureFormat::Depth24Plus, None),
+            (TextureFormat::Depth24PlusStencil8, None),
+        ];
+        for &(format, size) in sizes.iter() {
+            assert_eq!(format.block_size(None), size, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn format_block_size_aspects() {
+        let format = TextureFormat::Depth24PlusStencil8;
+        assert_eq!(format.block_size(Some(TextureAspect::StencilOnly)), Some(1));
+        assert_eq!(format.block_size(Some(TextureAspect::DepthOnly)), None);
+        assert_eq!(
+            TextureFormat::Rgba8Unorm.block_size(Some(TextureAspect::DepthOnly)),
+            None
+        );
+        assert_eq!(
+            TextureFormat::Depth32Float.block_size(Some(TextureAspect::DepthOnly)),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn format_block_dimensions() {
+        assert_eq!(TextureFormat::Rgba8Unorm.block_dimensions(), (1, 1));
+        assert_eq!(TextureFormat::Bc1RgbaUnorm.block_dimensions(), (4, 4));
+    }
+
+    #[test]
+    fn format_required_features() {
+        assert_eq!(
+            TextureFormat::Bc1RgbaUnorm.required_features(),
+            Features::TEXTURE_COMPRESSION_BC
+        );
+        assert_eq!(
+            TextureFormat::Bc7RgbaUnormSrgb.required_features(),
+            Features::TEXTURE_COMPRESSION_BC
+        );
+        assert_eq!(
+            TextureFormat::Rgba8Unorm.required_features(),
+            Features::empty()
+        );
+        assert_eq!(
+            TextureFormat::Rgba8Unorm.required_downlevel_flags(TextureUsage::SAMPLED),
+            DownlevelFlags::empty()
+        );
+        assert_eq!(
+            TextureFormat::Rgba8Unorm
+                .required_downlevel_flags(TextureUsage::STORAGE | TextureUsage::COPY_SRC),
+            DownlevelFlags::STORAGE_IMAGES
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::{error, fmt::Display, future::Future, mem::size_of, time::Duration};
+
+use super::read_buffer;
+
+/// [`Features::TIMESTAMP_QUERY`](crate::Features::TIMESTAMP_QUERY) is not enabled on the device.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TimestampQueryDisabled;
+
+impl Display for TimestampQueryDisabled {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Features::TIMESTAMP_QUERY must be enabled to time GPU work"
+        )
+    }
+}
+
+impl error::Error for TimestampQueryDisabled {}
+
+/// Converts the delta between two raw timestamp query values into nanoseconds.
+///
+/// `period` is the number of nanoseconds per tick, as returned by
+/// [`Queue::get_timestamp_period`](crate::Queue::get_timestamp_period).
+///
+/// Returns `None` if `end` is before `start`, which happens when the counter wrapped around
+/// or the timestamps were not written in order.
+pub fn timestamp_delta_ns(start: u64, end: u64, period: f32) -> Option<f64> {
+    end.checked_sub(start)
+        .map(|ticks| ticks as f64 * period as f64)
+}
+
+/// Measures the GPU time spent between pairs of timestamps, called spans.
+///
+/// For each frame:
+///
+/// - Call [`begin`](GpuTimer::begin) and [`end`](GpuTimer::end) around the work to time,
+///   once for each span index.
+/// - Call [`resolve`](GpuTimer::resolve) after all spans were recorded.
+/// - Submit the command encoders.
+/// - Call [`read_durations`](GpuTimer::read_durations).
+pub struct GpuTimer {
+    query_set: crate::QuerySet,
+    resolve_buffer: crate::Buffer,
+    span_count: u32,
+    timestamp_period: f32,
+}
+
+impl GpuTimer {
+    /// Allocates the queries and readback buffer for `span_count` spans.
+    ///
+    /// # Panics
+    ///
+    /// - `span_count` is zero.
+    pub fn new(
+        device: &crate::Device,
+        queue: &crate::Queue,
+        span_count: u32,
+    ) -> Result<Self, TimestampQueryDisabled> {
+        assert!(span_count > 0, "A GpuTimer needs at least one span");
+        if !device.features().contains(crate::Features::TIMESTAMP_QUERY) {
+            return Err(TimestampQueryDisabled);
+        }
+
+        let query_set = device.create_query_set(&crate::QuerySetDescriptor {
+            count: span_count * 2,
+            ty: crate::QueryType::Timestamp,
+        });
+        let resolve_buffer = device.create_buffer(&crate::BufferDescriptor {
+            label: Some("timestamp resolve"),
+            size: span_count as crate::BufferAddress * 2 * size_of::<u64>() as crate::BufferAddress,
+            usage: crate::BufferUsage::COPY_DST | crate::BufferUsage::MAP_READ,
+            mapped_at_creation: false,
+        });
+        Ok(Self {
+            query_set,
+            resolve_buffer,
+            span_count,
+            timestamp_period: queue.get_timestamp_period(),
+        })
+    }
+
+    /// Records the start timestamp of span `span`.
+    pub fn begin(&self, encoder: &mut crate::CommandEncoder, span: u32) {
+        assert!(span < self.span_count, "Span {} is out of range", span);
+        encoder.write_timestamp(&self.query_set, span * 2);
+    }
+
+    /// Records the end timestamp of span `span`.
+    pub fn end(&self, encoder: &mut crate::CommandEncoder, span: u32) {
+        assert!(span < self.span_count, "Span {} is out of range", span);
+        encoder.write_timestamp(&self.query_set, span * 2 + 1);
+    }
+
+    /// Resolves all the spans into the readback buffer.
+    pub fn resolve(&self, encoder: &mut crate::CommandEncoder) {
+        encoder.resolve_query_set(
+            &self.query_set,
+            0..self.span_count * 2,
+            &self.resolve_buffer,
+            0,
+        );
+    }
+
+    /// Reads back the duration of every span, once the resolving commands were submitted.
+    ///
+    /// The device is polled while waiting, so no external poller needs to be running.
+    pub fn read_durations<'a>(
+        &'a self,
+        device: &crate::Device,
+    ) -> impl Future<Output = Result<Vec<Duration>, crate::BufferAsyncError>> + Send + 'a {
+        let timestamp_period = self.timestamp_period;
+        let data = read_buffer(device, &self.resolve_buffer, ..);
+        async move {
+            let data = data.await?;
+            let durations = data
+                .chunks_exact(2 * size_of::<u64>())
+                .map(|span| {
+                    let mut start = [0; 8];
+                    let mut end = [0; 8];
+                    start.copy_from_slice(&span[..8]);
+                    end.copy_from_slice(&span[8..]);
+                    let nanos = timestamp_delta_ns(
+                        u64::from_ne_bytes(start),
+                        u64::from_ne_bytes(end),
+                        timestamp_period,
+                    );
+                    Duration::from_nanos(nanos.unwrap_or(0.0) as u64)
+                })
+                .collect();
+            Ok(durations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::timestamp_delta_ns;
+
+    #[test]
+    fn timestamp_delta_normal() {
+        assert_eq!(timestamp_delta_ns(100, 350, 1.0), Some(250.0));
+        assert_eq!(timestamp_delta_ns(100, 350, 2.5), Some(625.0));
+    }
+
+    #[test]
+    fn timestamp_delta_zero() {
+        assert_eq!(timestamp_delta_ns(42, 42, 83.333), Some(0.0));
+    }
+
+    #[test]
+    fn timestamp_delta_wrapped() {
+        assert_eq!(timestamp_delta_ns(u64::MAX - 10, 5, 1.0), None);
+    }
+}
@@ -0,0 +1,208 @@
+use std::{error, fmt::Display, marker::PhantomData, mem::size_of};
+
+use super::{BufferInitDescriptor, DeviceExt};
+
+/// Uniform buffer holding a single value of type `T`.
+///
+/// `T` has to match the layout the shader expects, including the padding required
+/// by the uniform address space: a `vec3<f32>` is aligned to 16 bytes, so it has to be
+/// followed by a 4 byte field or explicit padding. The size of `T` must be a multiple of 16.
+#[derive(Debug)]
+pub struct UniformBuffer<T> {
+    buffer: crate::Buffer,
+    _marker: PhantomData<T>,
+}
+
+impl<T: bytemuck::Pod> UniformBuffer<T> {
+    /// Creates a uniform buffer initialized with `value`.
+    ///
+    /// # Panics
+    ///
+    /// - The size of `T` is not a multiple of 16.
+    pub fn new(device: &crate::Device, label: crate::Label, value: &T) -> Self {
+        assert_eq!(
+            size_of::<T>() % 16,
+            0,
+            "Uniform type size {} is not a multiple of 16, add padding at the end",
+            size_of::<T>()
+        );
+        let buffer = device.create_buffer_init(&BufferInitDescriptor {
+            label,
+            contents: bytemuck::bytes_of(value),
+            usage: crate::BufferUsage::UNIFORM | crate::BufferUsage::COPY_DST,
+        });
+        Self {
+            buffer,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Schedules writing `value` into the buffer.
+    pub fn write(&self, queue: &crate::Queue, value: &T) {
+        queue.write_buffer(&self.buffer, 0, bytemuck::bytes_of(value));
+    }
+
+    /// The underlying buffer.
+    pub fn buffer(&self) -> &crate::Buffer {
+        &self.buffer
+    }
+
+    /// Binding of the whole buffer, for a [`BindGroupEntry`](crate::BindGroupEntry).
+    pub fn binding(&self) -> crate::BindingResource {
+        self.buffer.as_entire_binding()
+    }
+}
+
+/// Every slot of a [`DynamicUniformBuffer`] is in use.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DynamicUniformBufferFull;
+
+impl Display for DynamicUniformBufferFull {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Dynamic uniform buffer is full")
+    }
+}
+
+impl error::Error for DynamicUniformBufferFull {}
+
+/// Hands out consecutive aligned offsets into a buffer of `capacity` elements.
+#[derive(Debug)]
+struct OffsetAllocator {
+    stride: crate::BufferAddress,
+    capacity: u32,
+    len: u32,
+}
+
+impl OffsetAllocator {
+    fn new(element_size: crate::BufferAddress, capacity: u32) -> Self {
+        Self {
+            stride: super::align_to(element_size.max(1), crate::BIND_BUFFER_ALIGNMENT),
+            capacity,
+            len: 0,
+        }
+    }
+
+    fn allocate(&mut self) -> Result<crate::DynamicOffset, DynamicUniformBufferFull> {
+        if self.len == self.capacity {
+            return Err(DynamicUniformBufferFull);
+        }
+        let offset = self.len as crate::BufferAddress * self.stride;
+        self.len += 1;
+        Ok(offset as crate::DynamicOffset)
+    }
+}
+
+/// Uniform buffer holding up to `capacity` values of type `T`, one per draw or dispatch,
+/// bound with dynamic offsets.
+///
+/// Each value is placed at an offset aligned to [`BIND_BUFFER_ALIGNMENT`](crate::BIND_BUFFER_ALIGNMENT).
+/// The binding must be declared with `has_dynamic_offset: true`, and the offsets returned by
+/// [`push`](DynamicUniformBuffer::push) passed to `set_bind_group`. Call
+/// [`reset`](DynamicUniformBuffer::reset) at the start of every frame.
+///
+/// The size of `T` must be a multiple of 16, see [`UniformBuffer`].
+#[derive(Debug)]
+pub struct DynamicUniformBuffer<T> {
+    buffer: crate::Buffer,
+    offsets: OffsetAllocator,
+    _marker: PhantomData<T>,
+}
+
+impl<T: bytemuck::Pod> DynamicUniformBuffer<T> {
+    /// Creates a buffer with room for `capacity` values.
+    ///
+    /// # Panics
+    ///
+    /// - The size of `T` is not a multiple of 16.
+    pub fn new(device: &crate::Device, label: crate::Label, capacity: u32) -> Self {
+        assert_eq!(
+            size_of::<T>() % 16,
+            0,
+            "Uniform type size {} is not a multiple of 16, add padding at the end",
+            size_of::<T>()
+        );
+        let offsets = OffsetAllocator::new(size_of::<T>() as crate::BufferAddress, capacity);
+        let buffer = device.create_buffer(&crate::BufferDescriptor {
+            label,
+            size: offsets.stride * capacity as crate::BufferAddress,
+            usage: crate::BufferUsage::UNIFORM | crate::BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        Self {
+            buffer,
+            offsets,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Schedules writing `value` into the next free slot, returning its dynamic offset.
+    pub fn push(
+        &mut self,
+        queue: &crate::Queue,
+        value: &T,
+    ) -> Result<crate::DynamicOffset, DynamicUniformBufferFull> {
+        let offset = self.offsets.allocate()?;
+        queue.write_buffer(
+            &self.buffer,
+            offset as crate::BufferAddress,
+            bytemuck::bytes_of(value),
+        );
+        Ok(offset)
+    }
+
+    /// Frees all the slots, once the commands using them were submitted.
+    pub fn reset(&mut self) {
+        self.offsets.len = 0;
+    }
+
+    /// Number of values pushed since the last reset.
+    pub fn len(&self) -> u32 {
+        self.offsets.len
+    }
+
+    /// Returns true if no value was pushed since the last reset.
+    pub fn is_empty(&self) -> bool {
+        self.offsets.len == 0
+    }
+
+    /// The underlying buffer.
+    pub fn buffer(&self) -> &crate::Buffer {
+        &self.buffer
+    }
+
+    /// Binding of a single value, for a [`BindGroupEntry`](crate::BindGroupEntry).
+    pub fn binding(&self) -> crate::BindingResource {
+        crate::BindingResource::Buffer(crate::BufferBinding {
+            buffer: &self.buffer,
+            offset: 0,
+            size: crate::BufferSize::new(size_of::<T>() as crate::BufferAddress),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DynamicUniformBufferFull, OffsetAllocator};
+
+    #[test]
+    fn dynamic_offsets_are_aligned() {
+        let mut offsets = OffsetAllocator::new(80, 3);
+        let allocated = [
+            offsets.allocate().unwrap(),
+            offsets.allocate().unwrap(),
+            offsets.allocate().unwrap(),
+        ];
+        assert_eq!(allocated, [0, 256, 512]);
+        assert_eq!(offsets.allocate(), Err(DynamicUniformBufferFull));
+
+        offsets.len = 0;
+        assert_eq!(offsets.allocate(), Ok(0));
+    }
+
+    #[test]
+    fn dynamic_offsets_large_elements() {
+        let mut offsets = OffsetAllocator::new(272, 2);
+        assert_eq!(offsets.allocate(), Ok(0));
+        assert_eq!(offsets.allocate(), Ok(512));
+    }
+}
@@ -0,0 +1,102 @@
+use wgt::{AdapterInfo, DeviceType};
+
+/// PCI vendor IDs of the common GPU vendors, with their names.
+const VENDORS: &[(usize, &str)] = &[
+    (0x10de, "NVIDIA"),
+    (0x1002, "AMD"),
+    (0x1022, "AMD"),
+    (0x8086, "Intel"),
+    (0x106b, "Apple"),
+    (0x13b5, "ARM"),
+    (0x5143, "Qualcomm"),
+];
+
+/// Lowercase name fragments of the common software rasterizers.
+const SOFTWARE_ADAPTERS: &[&str] = &[
+    "llvmpipe",
+    "softpipe",
+    "swiftshader",
+    "microsoft basic render driver",
+];
+
+/// Hardware identification helpers, to branch on driver quirks.
+pub trait AdapterInfoExt {
+    /// Whether the adapter is a software rasterizer, such as llvmpipe, SwiftShader or WARP.
+    ///
+    /// Detected from the name, as not every backend reports them as [`DeviceType::Cpu`].
+    fn is_software(&self) -> bool;
+
+    /// Whether the adapter is a discrete GPU.
+    fn is_discrete(&self) -> bool;
+
+    /// Name of the vendor of the adapter, from its PCI vendor ID.
+    ///
+    /// Returns `"Unknown"` for vendors other than NVIDIA, AMD, Intel, Apple, ARM and Qualcomm.
+    fn vendor_name(&self) -> &'static str;
+}
+
+impl AdapterInfoExt for AdapterInfo {
+    fn is_software(&self) -> bool {
+        let name = self.name.to_lowercase();
+        self.device_type == DeviceType::Cpu
+            || SOFTWARE_ADAPTERS
+                .iter()
+                .any(|software| name.contains(software))
+    }
+
+    fn is_discrete(&self) -> bool {
+        self.device_type == DeviceType::DiscreteGpu
+    }
+
+    fn vendor_name(&self) -> &'static str {
+        VENDORS
+            .iter()
+            .find(|&&(vendor, _)| vendor == self.vendor)
+            .map_or("Unknown", |&(_, name)| name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AdapterInfoExt;
+    use wgt::{AdapterInfo, Backend, DeviceType};
+
+    fn info(name: &str, vendor: usize, device_type: DeviceType) -> AdapterInfo {
+        AdapterInfo {
+            name: name.to_owned(),
+            vendor,
+            device: 0,
+            device_type,
+            backend: Backend::Vulkan,
+        }
+    }
+
+    #[test]
+    fn vendor_names() {
+        let name = |vendor| info("", vendor, DeviceType::Other).vendor_name();
+        assert_eq!(name(0x10de), "NVIDIA");
+        assert_eq!(name(0x1002), "AMD");
+        assert_eq!(name(0x8086), "Intel");
+        assert_eq!(name(0x106b), "Apple");
+        assert_eq!(name(0x13b5), "ARM");
+        assert_eq!(name(0x5143), "Qualcomm");
+        assert_eq!(name(0x1234), "Unknown");
+    }
+
+    #[test]
+    fn software_adapters() {
+        // llvmpipe is reported as a CPU by Vulkan, but not by every backend
+        let llvmpipe = info(
+            "llvmpipe (LLVM 12.0.0, 256 bits)",
+            0x10005,
+            DeviceType::Other,
+        );
+        assert!(llvmpipe.is_software());
+        assert!(!llvmpipe.is_discrete());
+        assert!(info("Custom rasterizer", 0, DeviceType::Cpu).is_software());
+
+        let discrete = info("NVIDIA GeForce RTX 3080", 0x10de, DeviceType::DiscreteGpu);
+        assert!(!discrete.is_software());
+        assert!(discrete.is_discrete());
+    }
+}
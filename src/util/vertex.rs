@@ -0,0 +1,58 @@
+use wgt::{BufferAddress, VertexFormat};
+
+/// Alignment helpers complementing [`VertexFormat::size`].
+pub trait VertexFormatExt {
+    /// Required alignment of a vertex attribute of this format, in bytes.
+    ///
+    /// Per the WebGPU spec, this is the smaller of the format size and 4.
+    fn alignment(&self) -> BufferAddress;
+
+    /// Rounds `current` up to the next offset suitable for an attribute of this format.
+    fn aligned_offset(&self, current: BufferAddress) -> BufferAddress;
+}
+
+impl VertexFormatExt for VertexFormat {
+    fn alignment(&self) -> BufferAddress {
+        self.size().min(4)
+    }
+
+    fn aligned_offset(&self, current: BufferAddress) -> BufferAddress {
+        let align_mask = self.alignment() - 1;
+        (current + align_mask) & !align_mask
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VertexFormatExt;
+    use wgt::VertexFormat;
+
+    #[test]
+    fn vertex_format_alignment() {
+        let table = [
+            (VertexFormat::Uint8x2, 2),
+            (VertexFormat::Snorm8x2, 2),
+            (VertexFormat::Unorm8x4, 4),
+            (VertexFormat::Sint16x2, 4),
+            (VertexFormat::Float16x4, 4),
+            (VertexFormat::Float32, 4),
+            (VertexFormat::Float32x2, 4),
+            (VertexFormat::Float32x3, 4),
+            (VertexFormat::Float32x4, 4),
+            (VertexFormat::Uint32, 4),
+            (VertexFormat::Sint32x3, 4),
+        ];
+        for &(format, alignment) in table.iter() {
+            assert_eq!(format.alignment(), alignment, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn vertex_format_aligned_offset() {
+        assert_eq!(VertexFormat::Float32x4.aligned_offset(0), 0);
+        assert_eq!(VertexFormat::Float32x4.aligned_offset(12), 12);
+        assert_eq!(VertexFormat::Float32.aligned_offset(2), 4);
+        assert_eq!(VertexFormat::Uint8x2.aligned_offset(3), 4);
+        assert_eq!(VertexFormat::Uint8x2.aligned_offset(6), 6);
+    }
+}
@@ -0,0 +1,158 @@
+use std::{
+    borrow::Cow,
+    fs,
+    path::{Path, PathBuf},
+    sync::mpsc,
+    time::Duration,
+};
+
+use notify::{DebouncedEvent, RecursiveMode, Watcher};
+
+/// Editors often save a file in several steps, which are merged into a single reload.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// Watches a WGSL file, recompiling it whenever it changes on disk.
+///
+/// Requires both the `notify` and `naga` features.
+///
+/// ```ignore
+/// if let Some(result) = watcher.try_reload(&device) {
+///     match result {
+///         Ok(module) => pipeline = create_pipeline(&device, &module),
+///         // Keep using the previous module until the error is fixed
+///         Err(err) => log::error!("{}", err),
+///     }
+/// }
+/// ```
+pub struct ShaderWatcher {
+    path: PathBuf,
+    // Never used directly, but watching stops once it is dropped.
+    _watcher: notify::RecommendedWatcher,
+    events: mpsc::Receiver<DebouncedEvent>,
+}
+
+impl ShaderWatcher {
+    /// Starts watching the WGSL file at `path`.
+    pub fn new(path: impl AsRef<Path>) -> notify::Result<Self> {
+        let path = fs::canonicalize(path)?;
+        let (sender, events) = mpsc::channel();
+        let mut watcher = notify::watcher(sender, DEBOUNCE)?;
+        // Watch the parent directory, so the file is still tracked when editors
+        // replace it instead of writing to it.
+        watcher.watch(
+            path.parent().unwrap_or_else(|| Path::new("/")),
+            RecursiveMode::NonRecursive,
+        )?;
+        Ok(Self {
+            path,
+            _watcher: watcher,
+            events,
+        })
+    }
+
+    /// Path of the watched file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Recompiles the shader if the file changed since the last call.
+    ///
+    /// Returns `None` if nothing changed, and an error message if the new source
+    /// can't be read or fails validation.
+    pub fn try_reload(
+        &self,
+        device: &crate::Device,
+    ) -> Option<Result<crate::ShaderModule, String>> {
+        if !self.changed() {
+            return None;
+        }
+        Some(self.load(device))
+    }
+
+    fn changed(&self) -> bool {
+        // Drain all the pending events, so a change is only reported once
+        events_change_file(self.events.try_iter(), &self.path)
+    }
+
+    fn load(&self, device: &crate::Device) -> Result<crate::ShaderModule, String> {
+        let source = fs::read_to_string(&self.path).map_err(|err| err.to_string())?;
+        super::validate_wgsl(&source)?;
+        Ok(device.create_shader_module(&crate::ShaderModuleDescriptor {
+            label: self.path.to_str(),
+            source: crate::ShaderSource::Wgsl(Cow::Owned(source)),
+            flags: crate::ShaderFlags::all(),
+        }))
+    }
+}
+
+/// Checks whether any of `events` creates, writes or renames a file to `path`.
+fn events_change_file(events: impl Iterator<Item = DebouncedEvent>, path: &Path) -> bool {
+    let mut changed = false;
+    // Consume every event, even once a change was found
+    for event in events {
+        changed |= match event {
+            DebouncedEvent::Create(event_path) | DebouncedEvent::Write(event_path) => {
+                event_path == path
+            }
+            DebouncedEvent::Rename(_, to) => to == path,
+            _ => false,
+        };
+    }
+    changed
+}
+
+impl std::fmt::Debug for ShaderWatcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShaderWatcher")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use notify::DebouncedEvent;
+    use std::{
+        path::{Path, PathBuf},
+        sync::mpsc,
+    };
+
+    #[test]
+    fn events_change_file() {
+        let path = Path::new("/shaders/shader.wgsl");
+        let other = PathBuf::from("/shaders/other.wgsl");
+        let changes =
+            |events: Vec<DebouncedEvent>| super::events_change_file(events.into_iter(), path);
+
+        assert!(!changes(vec![]));
+        assert!(changes(vec![DebouncedEvent::Write(path.to_path_buf())]));
+        assert!(changes(vec![DebouncedEvent::Create(path.to_path_buf())]));
+        // Editors saving through a temporary file
+        assert!(changes(vec![
+            DebouncedEvent::Create(other.clone()),
+            DebouncedEvent::Rename(other.clone(), path.to_path_buf()),
+        ]));
+        assert!(!changes(vec![
+            DebouncedEvent::Write(other.clone()),
+            DebouncedEvent::Rename(path.to_path_buf(), other),
+            DebouncedEvent::NoticeWrite(path.to_path_buf()),
+            DebouncedEvent::Remove(path.to_path_buf()),
+        ]));
+    }
+
+    #[test]
+    fn events_change_file_drains_events() {
+        let path = Path::new("/shaders/shader.wgsl");
+        let (sender, events) = mpsc::channel();
+        sender
+            .send(DebouncedEvent::Write(path.to_path_buf()))
+            .unwrap();
+        sender
+            .send(DebouncedEvent::Write(path.to_path_buf()))
+            .unwrap();
+
+        // A change is only reported once
+        assert!(super::events_change_file(events.try_iter(), path));
+        assert!(!super::events_change_file(events.try_iter(), path));
+    }
+}
@@ -0,0 +1,258 @@
+//! Tests that need a GPU. Each of them passes without doing anything when no adapter is
+//! available, so they can run everywhere.
+#![cfg(not(target_arch = "wasm32"))]
+
+use wgpu::util::{DeviceExt, DownloadBuffer};
+
+fn initialize_device() -> Option<(wgpu::Device, wgpu::Queue)> {
+    let instance = wgpu::Instance::new(wgpu::BackendBit::PRIMARY);
+    let adapter =
+        pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions::default()))?;
+    pollster::block_on(adapter.request_device(
+        &wgpu::DeviceDescriptor {
+            label: None,
+            features: wgpu::Features::empty(),
+            limits: wgpu::Limits::default(),
+        },
+        None,
+    ))
+    .ok()
+}
+
+fn read_buffer(device: &wgpu::Device, queue: &wgpu::Queue, buffer: &wgpu::Buffer) -> Vec<u8> {
+    let download = DownloadBuffer::read_buffer(device, queue, &buffer.slice(..));
+    device.poll(wgpu::Maintain::Wait);
+    pollster::block_on(download).unwrap().to_vec()
+}
+
+#[test]
+fn clear_buffer_zeroes_range() {
+    let (device, queue) = match initialize_device() {
+        Some(device) => device,
+        None => return,
+    };
+    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: &[0xff; 64],
+        usage: wgpu::BufferUsage::COPY_SRC | wgpu::BufferUsage::COPY_DST,
+    });
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.clear_buffer(&buffer, 16, wgpu::BufferSize::new(32));
+    queue.submit(Some(encoder.finish()));
+
+    let contents = read_buffer(&device, &queue, &buffer);
+    assert!(contents[..16].iter().all(|&byte| byte == 0xff));
+    assert!(contents[16..48].iter().all(|&byte| byte == 0));
+    assert!(contents[48..].iter().all(|&byte| byte == 0xff));
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.clear_buffer(&buffer, 0, None);
+    queue.submit(Some(encoder.finish()));
+    assert!(read_buffer(&device, &queue, &buffer)
+        .iter()
+        .all(|&byte| byte == 0));
+}
+
+#[test]
+fn write_buffer_with_reads_back() {
+    let (device, queue) = match initialize_device() {
+        Some(device) => device,
+        None => return,
+    };
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: wgpu::BufferUsage::COPY_SRC | wgpu::BufferUsage::COPY_DST,
+        mapped_at_creation: false,
+    });
+
+    queue.write_buffer(&buffer, 0, &[1; 16]);
+    {
+        let mut view = queue
+            .write_buffer_with(&buffer, 4, wgpu::BufferSize::new(8).unwrap())
+            .unwrap();
+        view.copy_from_slice(&[2; 8]);
+    }
+    queue.submit(None);
+
+    assert_eq!(
+        read_buffer(&device, &queue, &buffer),
+        [1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 1, 1, 1, 1]
+    );
+}
+
+#[test]
+fn find_adapter_prefers_discrete_for_high_performance() {
+    let instance = wgpu::Instance::new(wgpu::BackendBit::PRIMARY);
+    let adapters: Vec<_> = instance
+        .enumerate_adapters(wgpu::BackendBit::PRIMARY)
+        .map(|adapter| adapter.get_info().device_type)
+        .collect();
+    let found = instance.find_adapter(
+        wgpu::BackendBit::PRIMARY,
+        wgpu::PowerPreference::HighPerformance,
+        None,
+    );
+    if adapters.is_empty() {
+        assert!(found.is_none());
+        return;
+    }
+    let device_type = found.unwrap().get_info().device_type;
+    if adapters.contains(&wgpu::DeviceType::DiscreteGpu) {
+        assert_eq!(device_type, wgpu::DeviceType::DiscreteGpu);
+    }
+}
+
+#[test]
+fn mapped_guard_unmaps_for_next_map() {
+    let (device, _queue) = match initialize_device() {
+        Some(device) => device,
+        None => return,
+    };
+    let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: &[1, 2, 3, 4, 5, 6, 7, 8],
+        usage: wgpu::BufferUsage::MAP_READ,
+    });
+    let slice = buffer.slice(..);
+
+    for _ in 0..2 {
+        let mapping = slice.map_async(wgpu::MapMode::Read);
+        device.poll(wgpu::Maintain::Wait);
+        pollster::block_on(mapping).unwrap();
+        let guard = slice.mapped_guard();
+        assert_eq!(*guard, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+}
+
+#[test]
+fn mapped_guard_panics_before_map_is_done() {
+    let (device, _queue) = match initialize_device() {
+        Some(device) => device,
+        None => return,
+    };
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 8,
+        usage: wgpu::BufferUsage::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let slice = buffer.slice(..);
+
+    let mapping = slice.map_async(wgpu::MapMode::Read);
+    // Nothing polled the device, so the mapping can't have completed yet
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        slice.mapped_guard();
+    }));
+    assert!(result.is_err());
+    device.poll(wgpu::Maintain::Wait);
+    pollster::block_on(mapping).unwrap();
+    assert_eq!(*slice.mapped_guard(), [0; 8]);
+}
+
+#[test]
+fn submit_with_completion_calls_back_once() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    let (device, queue) = match initialize_device() {
+        Some(device) => device,
+        None => return,
+    };
+    let buffer = |usage| {
+        device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: 256,
+            usage,
+            mapped_at_creation: false,
+        })
+    };
+    let source = buffer(wgpu::BufferUsage::COPY_SRC);
+    let destination = buffer(wgpu::BufferUsage::COPY_DST);
+
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.copy_buffer_to_buffer(&source, 0, &destination, 0, 256);
+    let index =
+        wgpu::util::submit_with_completion(&queue, &device, Some(encoder.finish()), move || {
+            counter.fetch_add(1, Ordering::SeqCst);
+        });
+    device.poll(wgpu::Maintain::WaitForSubmissionIndex(index));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    // Later polls and submissions don't run it again
+    let counter = Arc::clone(&calls);
+    queue.on_submitted_work_done(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    queue.submit(None);
+    device.poll(wgpu::Maintain::Wait);
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn submit_and_wait_completes_copy() {
+    let (device, queue) = match initialize_device() {
+        Some(device) => device,
+        None => return,
+    };
+    let source = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+        label: None,
+        contents: &[7; 16],
+        usage: wgpu::BufferUsage::COPY_SRC,
+    });
+    let destination = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 16,
+        usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+        mapped_at_creation: false,
+    });
+
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.copy_buffer_to_buffer(&source, 0, &destination, 0, 16);
+    queue.submit_and_wait(&device, Some(encoder.finish()));
+
+    // Map callbacks run when the device is maintained, which submitting does without
+    // waiting for anything, and the copy is done already
+    let slice = destination.slice(..);
+    let mapping = slice.map_async(wgpu::MapMode::Read);
+    queue.submit(None);
+    pollster::block_on(mapping).unwrap();
+    assert_eq!(*slice.mapped_guard(), [7; 16]);
+}
+
+#[test]
+fn poll_timeout_completes_work() {
+    let (device, queue) = match initialize_device() {
+        Some(device) => device,
+        None => return,
+    };
+    let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+        label: None,
+        size: 1 << 20,
+        usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+        mapped_at_creation: false,
+    });
+    let mut encoder =
+        device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+    encoder.clear_buffer(&buffer, 0, None);
+    queue.submit(Some(encoder.finish()));
+
+    assert_eq!(
+        device.poll_timeout(std::time::Duration::from_secs(10)),
+        Ok(true)
+    );
+    // Nothing is left to wait for
+    assert_eq!(
+        device.poll_timeout(std::time::Duration::from_secs(0)),
+        Ok(true)
+    );
+}
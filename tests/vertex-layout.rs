@@ -0,0 +1,45 @@
+#![cfg(feature = "derive")]
+
+#[repr(C)]
+#[derive(Clone, Copy, wgpu::VertexLayout)]
+struct Vertex {
+    #[vertex(location = 0, format = Float32x3)]
+    position: [f32; 3],
+    _padding: u32,
+    #[vertex(location = 1, format = Float32x2)]
+    tex_coord: [f32; 2],
+    #[vertex(location = 2, format = Uint32)]
+    index: u32,
+}
+
+#[test]
+fn derived_layout_matches_manual() {
+    let manual = [
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32x3,
+            offset: 0,
+            shader_location: 0,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Float32x2,
+            offset: 16,
+            shader_location: 1,
+        },
+        wgpu::VertexAttribute {
+            format: wgpu::VertexFormat::Uint32,
+            offset: 24,
+            shader_location: 2,
+        },
+    ];
+    assert_eq!(Vertex::ATTRIBUTES.len(), manual.len());
+    for (attr, expected) in Vertex::ATTRIBUTES.iter().zip(manual.iter()) {
+        assert_eq!(attr.format, expected.format);
+        assert_eq!(attr.offset, expected.offset);
+        assert_eq!(attr.shader_location, expected.shader_location);
+    }
+
+    let layout = Vertex::layout(wgpu::InputStepMode::Instance);
+    assert_eq!(layout.array_stride, 28);
+    assert_eq!(layout.step_mode, wgpu::InputStepMode::Instance);
+    assert_eq!(layout.attributes.len(), manual.len());
+}
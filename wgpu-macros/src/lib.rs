@@ -0,0 +1,127 @@
+//! Derive macros for [wgpu](https://docs.rs/wgpu), re-exported by it behind the `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse::ParseStream, parse_macro_input, Data, DeriveInput, Fields, Ident, LitInt, Token};
+
+/// Derives the vertex buffer layout of a `#[repr(C)]` vertex struct.
+///
+/// Every field making up a vertex attribute is annotated with its shader location and
+/// [`VertexFormat`](https://docs.rs/wgpu/*/wgpu/enum.VertexFormat.html) variant.
+/// Fields without the attribute, such as explicit padding, are skipped:
+///
+/// ```ignore
+/// #[repr(C)]
+/// #[derive(Clone, Copy, wgpu::VertexLayout)]
+/// struct Vertex {
+///     #[vertex(location = 0, format = Float32x3)]
+///     position: [f32; 3],
+///     #[vertex(location = 1, format = Float32x2)]
+///     tex_coord: [f32; 2],
+/// }
+///
+/// let layout = Vertex::layout(wgpu::InputStepMode::Vertex);
+/// ```
+///
+/// This generates an associated `const ATTRIBUTES: &[wgpu::VertexAttribute]`, with offsets
+/// taken from the actual field offsets, and a `fn layout(step_mode) -> wgpu::VertexBufferLayout`
+/// using the struct size as the stride.
+///
+/// The generated code relies on `core::mem::offset_of!`, which needs Rust 1.77 or newer.
+#[proc_macro_derive(VertexLayout, attributes(vertex))]
+pub fn derive_vertex_layout(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match vertex_layout(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+struct VertexAttr {
+    location: LitInt,
+    format: Ident,
+}
+
+fn parse_vertex_attr(input: ParseStream) -> syn::Result<VertexAttr> {
+    let mut location = None;
+    let mut format = None;
+    while !input.is_empty() {
+        let key: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+        if key == "location" {
+            location = Some(input.parse::<LitInt>()?);
+        } else if key == "format" {
+            format = Some(input.parse::<Ident>()?);
+        } else {
+            return Err(syn::Error::new(
+                key.span(),
+                "expected `location` or `format`",
+            ));
+        }
+        if !input.is_empty() {
+            input.parse::<Token![,]>()?;
+        }
+    }
+    match (location, format) {
+        (Some(location), Some(format)) => Ok(VertexAttr { location, format }),
+        _ => Err(input.error("`#[vertex]` needs both `location = N` and `format = Format`")),
+    }
+}
+
+fn vertex_layout(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match input.data {
+        Data::Struct(ref data) => match data.fields {
+            Fields::Named(ref fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.ident.span(),
+                    "`VertexLayout` can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`VertexLayout` can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut attributes = Vec::new();
+    for field in fields {
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("vertex"))
+        {
+            let VertexAttr { location, format } = attr.parse_args_with(parse_vertex_attr)?;
+            let field_name = field.ident.as_ref().unwrap();
+            attributes.push(quote! {
+                ::wgpu::VertexAttribute {
+                    format: ::wgpu::VertexFormat::#format,
+                    offset: ::core::mem::offset_of!(Self, #field_name) as ::wgpu::BufferAddress,
+                    shader_location: #location,
+                }
+            });
+        }
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Vertex attributes of this struct, in declaration order.
+            pub const ATTRIBUTES: &'static [::wgpu::VertexAttribute] = &[#(#attributes),*];
+
+            /// Vertex buffer layout of an array of this struct.
+            pub fn layout(step_mode: ::wgpu::InputStepMode) -> ::wgpu::VertexBufferLayout<'static> {
+                ::wgpu::VertexBufferLayout {
+                    array_stride: ::core::mem::size_of::<Self>() as ::wgpu::BufferAddress,
+                    step_mode,
+                    attributes: Self::ATTRIBUTES,
+                }
+            }
+        }
+    })
+}